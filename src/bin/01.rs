@@ -1,34 +1,44 @@
+use advent_of_code::template::error::{Context, Result};
 use itertools::Itertools;
 
 advent_of_code::solution!(1);
 
-pub fn part_one(input: &str) -> Option<i32> {
-    let left = input
+fn parse(input: &str) -> Result<(Vec<i32>, Vec<i32>)> {
+    input
         .lines()
-        .map(|line| line.split_once("   ").unwrap().0.parse::<i32>().unwrap())
-        .sorted();
-    let right = input
-        .lines()
-        .map(|line| line.split_once("   ").unwrap().1.parse::<i32>().unwrap())
-        .sorted();
+        .enumerate()
+        .map(|(i, line)| {
+            let (l, r) = line
+                .split_once("   ")
+                .with_context(|| format!("line {}: expected two numbers", i + 1))?;
+            Ok((
+                l.parse::<i32>()
+                    .with_context(|| format!("line {}: invalid number `{l}`", i + 1))?,
+                r.parse::<i32>()
+                    .with_context(|| format!("line {}: invalid number `{r}`", i + 1))?,
+            ))
+        })
+        .collect()
+}
 
-    Some(left.zip(right).map(|(l, r)| (l - r).abs()).sum())
+pub fn part_one(input: &str) -> Result<i32> {
+    let (left, right) = parse(input)?;
+
+    Ok(left
+        .into_iter()
+        .sorted()
+        .zip(right.into_iter().sorted())
+        .map(|(l, r)| (l - r).abs())
+        .sum())
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
-    let left = input
-        .lines()
-        .map(|line| line.split_once("   ").unwrap().0.parse::<i32>().unwrap())
-        .sorted();
-    let right = input
-        .lines()
-        .map(|line| line.split_once("   ").unwrap().1.parse::<i32>().unwrap())
-        .sorted();
+pub fn part_two(input: &str) -> Result<i32> {
+    let (left, right) = parse(input)?;
 
-    Some(
-        left.map(|l| l * right.clone().filter(|&r| l == r).count() as i32)
-            .sum(),
-    )
+    Ok(left
+        .iter()
+        .map(|l| l * right.iter().filter(|&r| l == r).count() as i32)
+        .sum())
 }
 
 #[cfg(test)]
@@ -38,12 +48,21 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
+        assert_eq!(result.unwrap(), 11);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
+        assert_eq!(result.unwrap(), 31);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let error = part_one("3   4\n4   x\n").unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "line 2: invalid number `x`: invalid digit found in string"
+        );
     }
 }
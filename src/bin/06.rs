@@ -1,12 +1,20 @@
-use advent_of_code::{parse_char_matrix, Direction, Pos};
-use ndarray::Array2;
+use advent_of_code::{char_enum, Direction, Grid, Pos};
 
 // 2142 too low
 advent_of_code::solution!(6);
 
+char_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Floor = '.',
+        Obstruction = '#',
+        Guard = '^',
+    }
+}
+
 pub fn part_one(input: &str) -> Option<usize> {
-    let matrix = parse_char_matrix(input);
-    let pos = find_start_pos(&matrix);
+    let matrix: Grid<Tile> = input.parse().unwrap();
+    let pos = matrix.find(&Tile::Guard).unwrap();
     let dir = Direction::North;
 
     let mut seen = hashbrown::HashSet::new();
@@ -20,54 +28,39 @@ pub fn part_one(input: &str) -> Option<usize> {
 fn trace_path(
     mut pos: Pos<usize>,
     mut dir: Direction,
-    matrix: &Array2<char>,
+    matrix: &Grid<Tile>,
     mut seen: hashbrown::HashSet<Pos<usize>>,
 ) -> hashbrown::HashSet<Pos<usize>> {
     loop {
-        let Some(next_pos) = (pos + dir).in_bounds(matrix.dim()) else {
+        let Some(next_pos) = matrix.in_bounds(pos + dir) else {
             return seen;
         };
 
-        match matrix[[next_pos.0, next_pos.1]] {
-            '#' => dir = dir.turn_right(),
-            '.' | '^' => pos = next_pos,
-            _ => unreachable!(),
+        match matrix[next_pos] {
+            Tile::Obstruction => dir = dir.turn_right(),
+            Tile::Floor | Tile::Guard => pos = next_pos,
         }
 
         seen.insert(pos);
     }
 }
 
-fn find_start_pos(matrix: &Array2<char>) -> Pos<usize> {
-    let (rows, cols) = matrix.dim();
-
-    for r in 0..rows {
-        for c in 0..cols {
-            if matrix[[r, c]] == '^' {
-                return Pos(r, c);
-            }
-        }
-    }
-    unreachable!()
-}
-
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut matrix = parse_char_matrix(input);
-    let start_pos = find_start_pos(&matrix);
+    let matrix: Grid<Tile> = input.parse().unwrap();
+    let start_pos = matrix.find(&Tile::Guard).unwrap();
     let dir = Direction::North;
 
     let mut seen = hashbrown::HashSet::new();
     seen.insert(start_pos);
 
     let seen = trace_path(start_pos, dir, &matrix, seen);
-    matrix[[start_pos.0, start_pos.1]] = '^';
 
     Some(
         seen.into_iter()
             .skip(1)
             .filter(|box_pos| {
                 let mut matrix = matrix.clone();
-                matrix[[box_pos.0, box_pos.1]] = 'O';
+                matrix[*box_pos] = Tile::Obstruction;
 
                 is_loop(start_pos, dir, matrix)
             })
@@ -75,30 +68,24 @@ pub fn part_two(input: &str) -> Option<usize> {
     )
 }
 
-fn is_loop(mut start_pos: Pos<usize>, mut dir: Direction, matrix: Array2<char>) -> bool {
+fn is_loop(mut start_pos: Pos<usize>, mut dir: Direction, matrix: Grid<Tile>) -> bool {
     let mut seen = hashbrown::HashSet::new();
     loop {
         seen.insert((start_pos, dir));
 
-        let Some(next_pos) = (start_pos + dir).in_bounds(matrix.dim()) else {
+        let Some(next_pos) = matrix.in_bounds(start_pos + dir) else {
             return false;
         };
 
-        // match dir {
-        //     Direction::North | Direction::South => matrix[[start_pos.0, start_pos.1]] = '|',
-        //     Direction::East | Direction::West => matrix[[start_pos.0, start_pos.1]] = '-',
-        // }
-
-        match matrix[[next_pos.0, next_pos.1]] {
-            '#' | 'O' => dir = dir.turn_right(),
-            '.' | '^' | '|' | '-' => {
+        match matrix[next_pos] {
+            Tile::Obstruction => dir = dir.turn_right(),
+            Tile::Floor | Tile::Guard => {
                 start_pos = next_pos;
             }
-            _ => unreachable!(),
         }
 
         if seen.contains(&(start_pos, dir)) {
-            // print_matrix(&matrix);
+            // println!("{matrix}");
             return true;
         }
     }
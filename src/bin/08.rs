@@ -14,7 +14,7 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 fn get_antinodes(
-    antennas: &HashMap<char, Vec<Pos<usize>>>,
+    antennas: &HashMap<char, Vec<Pos<isize>>>,
     bounds: (usize, usize),
 ) -> HashSet<Pos<usize>> {
     let mut antinodes = HashSet::new();
@@ -33,7 +33,7 @@ fn get_antinodes(
     antinodes
 }
 
-fn get_antenna_map(input: &str) -> HashMap<char, Vec<Pos<usize>>> {
+fn get_antenna_map(input: &str) -> HashMap<char, Vec<Pos<isize>>> {
     let mut antennas = HashMap::new();
     for (r, line) in input.lines().enumerate() {
         for (c, char) in line.char_indices() {
@@ -41,7 +41,7 @@ fn get_antenna_map(input: &str) -> HashMap<char, Vec<Pos<usize>>> {
                 antennas
                     .entry(char)
                     .or_insert_with(Vec::new)
-                    .push(Pos(r, c));
+                    .push(Pos(r as isize, c as isize));
             }
         }
     }
@@ -56,7 +56,7 @@ pub fn part_two(input: &str) -> Option<usize> {
 }
 
 fn get_antinodes_2(
-    antennas: &HashMap<char, Vec<Pos<usize>>>,
+    antennas: &HashMap<char, Vec<Pos<isize>>>,
     bounds: (usize, usize),
 ) -> HashSet<Pos<usize>> {
     let mut antinodes = HashSet::new();
@@ -1,7 +1,4 @@
-use std::collections::VecDeque;
-
-use advent_of_code::{get_adjacent_positions, parse_int_matrix, Pos};
-use hashbrown::HashSet;
+use advent_of_code::{bfs, bfs_all, get_adjacent_positions, parse_int_matrix, Pos};
 use itertools::Itertools;
 use ndarray::Array2;
 
@@ -24,23 +21,19 @@ pub fn part_one(input: &str) -> Option<usize> {
 
 /// Count the number of summits reachable from the given position.
 fn count_summits(zero: Pos<usize>, matrix: &Array2<usize>) -> usize {
-    let mut seen = HashSet::<Pos<usize>>::from([zero]);
-    let mut queue = VecDeque::<(Pos<usize>, usize)>::from([(zero, 0)]);
-    let mut summits = 0;
-
-    while let Some((current, height)) = queue.pop_front() {
-        if height == 9 {
-            summits += 1;
-            continue;
-        }
-        let next = get_adjacent_positions(current, matrix.dim())
-            .filter(|&p| matrix[(p.0, p.1)] == height + 1)
-            .filter(|&p| !seen.contains(&p))
-            .collect_vec();
-        queue.extend(next.iter().map(|&p| (p, height + 1)));
-        seen.extend(&next);
-    }
-    summits
+    let reachable = bfs(
+        zero,
+        |&current| {
+            get_adjacent_positions(current, matrix.dim())
+                .filter(move |&p| matrix[p.tuple()] == matrix[current.tuple()] + 1)
+        },
+        |_| false,
+    );
+    reachable
+        .distances
+        .keys()
+        .filter(|p| matrix[p.tuple()] == 9)
+        .count()
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
@@ -53,28 +46,23 @@ pub fn part_two(input: &str) -> Option<usize> {
 
     zeros
         .iter()
-        .map(|&zero| count_summits_2(zero, &matrix))
+        .map(|&zero| count_trails(zero, &matrix))
         .sum::<usize>()
         .into()
 }
 
-/// Same as `count_summits`, but without the `seen` set, which causes multiple routes to the same
-/// summit to be counted.
-fn count_summits_2(zero: Pos<usize>, matrix: &Array2<usize>) -> usize {
-    let mut queue = VecDeque::<(Pos<usize>, usize)>::from([(zero, 0)]);
-    let mut summits = 0;
-
-    while let Some((current, height)) = queue.pop_front() {
-        if height == 9 {
-            summits += 1;
-            continue;
-        }
-        let next = get_adjacent_positions(current, matrix.dim())
-            .filter(|&p| matrix[(p.0, p.1)] == height + 1)
-            .collect_vec();
-        queue.extend(next.iter().map(|&p| (p, height + 1)));
-    }
-    summits
+/// Count the number of distinct trails from the given position to any summit. Every trail to a
+/// summit takes exactly 9 steps, so these are the shortest paths to all summits.
+fn count_trails(zero: Pos<usize>, matrix: &Array2<usize>) -> usize {
+    let trails = bfs_all(
+        zero,
+        |&current| {
+            get_adjacent_positions(current, matrix.dim())
+                .filter(move |&p| matrix[p.tuple()] == matrix[current.tuple()] + 1)
+        },
+        |p| matrix[p.tuple()] == 9,
+    );
+    trails.count_paths().try_into().unwrap()
 }
 
 #[cfg(test)]
@@ -3,7 +3,10 @@ use ndarray::array;
 use ndarray_linalg::Solve;
 use num::complex::ComplexFloat;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(13, variants: [
+    (1, "cramer", part_one_cramer),
+    (2, "cramer", part_two_cramer),
+]);
 
 pub fn part_one(input: &str) -> Option<u32> {
     Some(
@@ -63,6 +66,49 @@ pub fn part_two(input: &str) -> Option<u64> {
     )
 }
 
+/// Solves the equations of each claw machine exactly with Cramer's rule, instead of trying all
+/// button presses or solving them numerically.
+fn cramer(input: &str, offset: i64, max_presses: i64) -> i64 {
+    input
+        .split("\n\n")
+        .map(|chunk| {
+            let (ax, ay, bx, by, px, py) = chunk
+                .split(['+', ',', '=', '\n'])
+                .skip(1)
+                .step_by(2)
+                .map(|num| num.parse::<i64>().unwrap())
+                .collect_tuple()
+                .unwrap();
+            let (px, py) = (px + offset, py + offset);
+
+            let det = ax * by - ay * bx;
+            if det == 0 {
+                return 0;
+            }
+
+            let (a, b) = (px * by - py * bx, ax * py - ay * px);
+            if a % det != 0 || b % det != 0 {
+                return 0;
+            }
+
+            let (a, b) = (a / det, b / det);
+            if (0..=max_presses).contains(&a) && (0..=max_presses).contains(&b) {
+                3 * a + b
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+pub fn part_one_cramer(input: &str) -> Option<i64> {
+    Some(cramer(input, 0, 100))
+}
+
+pub fn part_two_cramer(input: &str) -> Option<i64> {
+    Some(cramer(input, 10000000000000, i64::MAX))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,4 +134,16 @@ Prize: X=18641, Y=10279";
         let result = part_one(INPUT);
         assert_eq!(result, Some(480));
     }
+
+    #[test]
+    fn test_part_one_cramer() {
+        let result = part_one_cramer(INPUT);
+        assert_eq!(result, Some(480));
+    }
+
+    #[test]
+    fn test_part_two_cramer() {
+        let result = part_two_cramer(INPUT);
+        assert_eq!(result, Some(875318608908));
+    }
 }
@@ -1,53 +1,66 @@
-use advent_of_code::{parse_char_matrix, Direction, Pos};
+use advent_of_code::{char_enum, Direction, Grid, Pos};
 use itertools::Itertools;
-use ndarray::{s, Array2};
+use ndarray::s;
 
 advent_of_code::solution!(15);
 
+char_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tile {
+        Wall = '#',
+        Empty = '.',
+        Robot = '@',
+        Box = 'O',
+        BoxLeft = '[',
+        BoxRight = ']',
+    }
+}
+
 pub fn part_one(input: &str) -> Option<usize> {
     let (mut matrix, dirs) = parse_input(input);
-    let robot: Pos<isize> = matrix
-        .indexed_iter()
-        .find_map(|(idx, &c)| if c == '@' { Some(idx) } else { None })
-        .map(|(r, c)| Pos(r as isize, c as isize))
-        .unwrap();
+    let robot = find_robot(&matrix);
 
     navigate_robot(dirs, &mut matrix, robot);
     Some(
         matrix
-            .indexed_iter()
-            .filter_map(|(idx, &c)| if c == 'O' { Some(idx) } else { None })
-            .map(|(r, c)| r * 100 + c)
+            .positions_of(&Tile::Box)
+            .map(|Pos(r, c)| r * 100 + c)
             .sum(),
     )
 }
 
-fn navigate_robot(dirs: Vec<Direction>, matrix: &mut Array2<char>, mut robot: Pos<isize>) {
+fn find_robot(matrix: &Grid<Tile>) -> Pos<isize> {
+    let Pos(r, c) = matrix.find(&Tile::Robot).unwrap();
+    Pos(r as isize, c as isize)
+}
+
+fn navigate_robot(dirs: Vec<Direction>, matrix: &mut Grid<Tile>, mut robot: Pos<isize>) {
     for dir in dirs {
-        match matrix[(robot + dir).tuple()] {
-            '.' => {
-                matrix[robot.tuple()] = '.';
+        match matrix[robot + dir] {
+            Tile::Empty => {
+                matrix[robot] = Tile::Empty;
                 robot += dir;
-                matrix[robot.tuple()] = '@';
+                matrix[robot] = Tile::Robot;
             }
-            '#' => (),
-            'O' => {
+            Tile::Wall => (),
+            Tile::Box => {
                 let slice = match dir {
                     Direction::North => s![..=robot.0; -1, robot.1],
                     Direction::East => s![robot.0, robot.1..],
                     Direction::South => s![robot.0.., robot.1],
                     Direction::West => s![robot.0, ..=robot.1; -1],
                 };
-                if let Some(c) = matrix
+                if let Some(tile) = matrix
+                    .as_array_mut()
                     .slice_mut(slice)
                     .iter_mut()
-                    .find(|c| **c == '.' || **c == '#')
+                    .find(|tile| **tile == Tile::Empty || **tile == Tile::Wall)
                 {
-                    if *c == '.' {
-                        *c = 'O';
-                        matrix[robot.tuple()] = '.';
+                    if *tile == Tile::Empty {
+                        *tile = Tile::Box;
+                        matrix[robot] = Tile::Empty;
                         robot += dir;
-                        matrix[robot.tuple()] = '@';
+                        matrix[robot] = Tile::Robot;
                     }
                 };
             }
@@ -56,9 +69,9 @@ fn navigate_robot(dirs: Vec<Direction>, matrix: &mut Array2<char>, mut robot: Po
     }
 }
 
-pub fn parse_input(input: &str) -> (Array2<char>, Vec<Direction>) {
+fn parse_input(input: &str) -> (Grid<Tile>, Vec<Direction>) {
     let (matrix_str, moves_str) = input.split_once("\n\n").unwrap();
-    let matrix = parse_char_matrix(matrix_str);
+    let matrix = matrix_str.parse().unwrap();
     let dirs = moves_str
         .chars()
         .filter(|&c| c != '\n')
@@ -67,9 +80,9 @@ pub fn parse_input(input: &str) -> (Array2<char>, Vec<Direction>) {
     (matrix, dirs)
 }
 
-pub fn parse_input_2(input: &str) -> (Array2<char>, Vec<Direction>) {
+fn parse_input_2(input: &str) -> (Grid<Tile>, Vec<Direction>) {
     let (matrix_str, moves_str) = input.split_once("\n\n").unwrap();
-    let matrix = part_char_array_part2(matrix_str);
+    let matrix = widen(matrix_str).parse().unwrap();
     let dirs = moves_str
         .chars()
         .filter(|&c| c != '\n')
@@ -78,74 +91,59 @@ pub fn parse_input_2(input: &str) -> (Array2<char>, Vec<Direction>) {
     (matrix, dirs)
 }
 
-/// This function is a copy of the `parse_char_matrix` function from the lib, but includes the
-/// modified interpretation of the characters of part 2.
-fn part_char_array_part2(
-    matrix_str: &str,
-) -> ndarray::ArrayBase<ndarray::OwnedRepr<char>, ndarray::Dim<[usize; 2]>> {
-    let cols = matrix_str
-        .lines()
-        .map(|l| l.len())
-        .reduce(|prev, next| {
-            assert_eq!(prev, next);
-            next
-        })
-        .unwrap();
-    let rows = matrix_str.lines().count();
-    let shape = (rows, cols * 2);
-    let chars = matrix_str.chars().filter(|c| *c != '\n').flat_map(|c| {
-        match c {
+/// Applies the modified interpretation of the characters of part 2, which doubles the width.
+fn widen(matrix_str: &str) -> String {
+    matrix_str
+        .chars()
+        .map(|c| match c {
             '#' => "##",
             '.' => "..",
             '@' => "@.",
             'O' => "[]",
+            '\n' => "\n",
             _ => unreachable!(),
-        }
-        .chars()
-    });
-    ndarray::Array::from_iter(chars)
-        .into_shape_clone((shape, ndarray::Order::RowMajor))
-        .unwrap()
+        })
+        .collect()
 }
 
-fn navigate_robot_2(dirs: Vec<Direction>, matrix: &mut Array2<char>, mut robot: Pos<isize>) {
+fn navigate_robot_2(dirs: Vec<Direction>, matrix: &mut Grid<Tile>, mut robot: Pos<isize>) {
     for dir in dirs {
-        match matrix[(robot + dir).tuple()] {
-            '.' => {
-                matrix[robot.tuple()] = '.';
+        match matrix[robot + dir] {
+            Tile::Empty => {
+                matrix[robot] = Tile::Empty;
                 robot += dir;
-                matrix[robot.tuple()] = '@';
+                matrix[robot] = Tile::Robot;
             }
-            '#' => (),
-            '[' => {
+            Tile::Wall => (),
+            Tile::BoxLeft => {
                 if let Some(movable_boxes) = box_moved(matrix, robot + dir, dir) {
                     for box_pos_left in movable_boxes.into_iter().unique() {
                         let box_pos_right = box_pos_left + Direction::East;
-                        matrix[(box_pos_right + dir).tuple()] = ']';
-                        matrix[box_pos_right.tuple()] = '.';
-                        matrix[(box_pos_left + dir).tuple()] = '[';
-                        matrix[box_pos_left.tuple()] = '.';
+                        matrix[box_pos_right + dir] = Tile::BoxRight;
+                        matrix[box_pos_right] = Tile::Empty;
+                        matrix[box_pos_left + dir] = Tile::BoxLeft;
+                        matrix[box_pos_left] = Tile::Empty;
                     }
-                    matrix[robot.tuple()] = '.';
+                    matrix[robot] = Tile::Empty;
                     robot += dir;
-                    matrix[robot.tuple()] = '@';
+                    matrix[robot] = Tile::Robot;
                 }
             }
-            ']' => {
+            Tile::BoxRight => {
                 if let Some(movable_boxes) = box_moved(matrix, robot + dir + Direction::West, dir) {
                     for box_pos_left in movable_boxes.into_iter().unique() {
                         let box_pos_right = box_pos_left + Direction::East;
-                        matrix[(box_pos_left + dir).tuple()] = '[';
-                        matrix[box_pos_left.tuple()] = '.';
-                        matrix[(box_pos_right + dir).tuple()] = ']';
-                        matrix[box_pos_right.tuple()] = '.';
+                        matrix[box_pos_left + dir] = Tile::BoxLeft;
+                        matrix[box_pos_left] = Tile::Empty;
+                        matrix[box_pos_right + dir] = Tile::BoxRight;
+                        matrix[box_pos_right] = Tile::Empty;
                     }
-                    matrix[robot.tuple()] = '.';
+                    matrix[robot] = Tile::Empty;
                     robot += dir;
-                    matrix[robot.tuple()] = '@';
+                    matrix[robot] = Tile::Robot;
                 }
             }
-            c => unreachable!("Invalid character: {:?}", c),
+            tile => unreachable!("Invalid tile: {:?}", tile),
         };
     }
 }
@@ -153,7 +151,7 @@ fn navigate_robot_2(dirs: Vec<Direction>, matrix: &mut Array2<char>, mut robot:
 /// If the given box can be moved in the given direction, returns the (left side) positions of all
 /// the boxes that will be moved, otherwise returns None.
 fn box_moved(
-    matrix: &Array2<char>,
+    matrix: &Grid<Tile>,
     box_left_side: Pos<isize>,
     dir: Direction,
 ) -> Option<Vec<Pos<isize>>> {
@@ -161,71 +159,66 @@ fn box_moved(
 
     match dir {
         Direction::North | Direction::South => {
-            match (
-                matrix[(box_left_side + dir).tuple()],
-                matrix[(box_right_side + dir).tuple()],
-            ) {
-                ('.', '.') => Some(vec![box_left_side]),
-                ('#', _) => None,
-                (_, '#') => None,
-                ('[', ']') => box_moved(matrix, box_left_side + dir, dir).map(|mut v| {
-                    v.push(box_left_side);
-                    v
-                }),
-                (']', '.') => {
+            match (matrix[box_left_side + dir], matrix[box_right_side + dir]) {
+                (Tile::Empty, Tile::Empty) => Some(vec![box_left_side]),
+                (Tile::Wall, _) => None,
+                (_, Tile::Wall) => None,
+                (Tile::BoxLeft, Tile::BoxRight) => {
+                    box_moved(matrix, box_left_side + dir, dir).map(|mut v| {
+                        v.push(box_left_side);
+                        v
+                    })
+                }
+                (Tile::BoxRight, Tile::Empty) => {
                     box_moved(matrix, box_left_side + dir + Direction::West, dir).map(|mut v| {
                         v.push(box_left_side);
                         v
                     })
                 }
-                ('.', '[') => box_moved(matrix, box_right_side + dir, dir).map(|mut v| {
-                    v.push(box_left_side);
-                    v
-                }),
-                (']', '[') => {
+                (Tile::Empty, Tile::BoxLeft) => {
+                    box_moved(matrix, box_right_side + dir, dir).map(|mut v| {
+                        v.push(box_left_side);
+                        v
+                    })
+                }
+                (Tile::BoxRight, Tile::BoxLeft) => {
                     let left_box = box_moved(matrix, box_left_side + dir + Direction::West, dir)?;
                     let right_box = box_moved(matrix, box_right_side + dir, dir)?;
                     Some([left_box, right_box, vec![box_left_side]].concat())
                 }
-                c => unreachable!("Invalid character: {:?}", c),
+                tile => unreachable!("Invalid tile: {:?}", tile),
             }
         }
-        Direction::East => match matrix[(box_right_side + dir).tuple()] {
-            '.' => Some(vec![box_left_side]),
-            '#' => None,
-            '[' => box_moved(matrix, box_right_side + dir, dir).map(|mut v| {
+        Direction::East => match matrix[box_right_side + dir] {
+            Tile::Empty => Some(vec![box_left_side]),
+            Tile::Wall => None,
+            Tile::BoxLeft => box_moved(matrix, box_right_side + dir, dir).map(|mut v| {
                 v.push(box_left_side);
                 v
             }),
-            c => unreachable!("Invalid character: {:?}", c),
+            tile => unreachable!("Invalid tile: {:?}", tile),
         },
-        Direction::West => match matrix[(box_left_side + dir).tuple()] {
-            '.' => Some(vec![box_left_side]),
-            '#' => None,
-            ']' => box_moved(matrix, box_left_side + dir + dir, dir).map(|mut v| {
+        Direction::West => match matrix[box_left_side + dir] {
+            Tile::Empty => Some(vec![box_left_side]),
+            Tile::Wall => None,
+            Tile::BoxRight => box_moved(matrix, box_left_side + dir + dir, dir).map(|mut v| {
                 v.push(box_left_side);
                 v
             }),
-            c => unreachable!("Invalid character: {:?}", c),
+            tile => unreachable!("Invalid tile: {:?}", tile),
         },
     }
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
     let (mut matrix, dirs) = parse_input_2(input);
-
-    let robot: Pos<isize> = matrix
-        .indexed_iter()
-        .find_map(|(idx, &c)| if c == '@' { Some(idx) } else { None })
-        .map(|(r, c)| Pos(r as isize, c as isize))
-        .unwrap();
+    let robot = find_robot(&matrix);
 
     navigate_robot_2(dirs, &mut matrix, robot);
     Some(
         matrix
-            .indexed_iter()
-            .filter_map(|(idx, &c)| if c == '[' { Some(idx) } else { None })
-            .map(|(r, c)| r * 100 + c)
+            .positions_of(&Tile::BoxLeft)
+            .map(|Pos(r, c)| r * 100 + c)
             .sum(),
     )
 }
@@ -15,7 +15,7 @@ struct Regs {
 pub fn part_one(input: &str) -> Option<Reg> {
     let (program, mut regs) = parse_input(input);
     let output = run(program, &mut regs);
-    let join = dbg!(output.into_iter().join(""));
+    let join = advent_of_code::debug_dbg!(output.into_iter().join(""));
     join.parse::<Reg>().ok()
 }
 
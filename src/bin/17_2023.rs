@@ -1,7 +1,4 @@
-use std::collections::VecDeque;
-
-use advent_of_code::{parse_int_matrix, Direction, Pos, DIRECTIONS};
-use hashbrown::HashMap;
+use advent_of_code::{dijkstra, parse_int_matrix, Direction, Pos, DIRECTIONS};
 use ndarray::Array2;
 advent_of_code::solution!(17);
 // 1. 872 too high
@@ -13,90 +10,39 @@ type Key = (Pos<Int>, Direction, u8); // u8 is the number of of steps in a strai
 pub fn part_one(input: &str) -> Option<Int> {
     let heat_matrix = parse_int_matrix::<Int>(input);
     let [rows, cols] = heat_matrix.shape().try_into().unwrap();
-    println!("{}", heat_matrix);
+    advent_of_code::debug_println!("{}", heat_matrix);
 
     let end = Pos(rows - 1, cols - 1);
-    let mut min_heat = HashMap::<Key, Int>::with_capacity(1024);
-
-    let start_east = (Pos(0, 1), Direction::East, 1);
-    min_heat.insert(start_east, get_heat(&heat_matrix, Pos(0, 1)));
-    let start_south = (Pos(1, 0), Direction::South, 1);
-    min_heat.insert(start_south, get_heat(&heat_matrix, Pos(1, 0)));
-
-    let mut queue = VecDeque::<Key>::new();
-    queue.push_back(start_east);
-    queue.push_back(start_south);
-    // let mut came_from = HashMap::<PosDir, PosDir>::with_capacity(1024);
-    // came_from.insert((start, Direction::East), (start, Direction::East));
-    // came_from.insert((start, Direction::South), (start, Direction::South));
-
-    while let Some(key) = queue.pop_front() {
-        let heat_to_current_pos = min_heat[&key];
-        for next_dir in DIRECTIONS {
-            let (pos, dir, straight_steps) = key;
-            let Some(next_pos) = (pos + next_dir).in_bounds([rows, cols]) else {
-                continue;
-            };
-
-            // Skip if we are moving back
-            if Pos::from(dir) + Pos::from(next_dir) == Pos(0, 0) {
-                continue;
-            }
-
-            let next_straight_steps = if dir == next_dir {
-                straight_steps + 1
-            } else {
-                1
-            };
-            if next_straight_steps > 3 {
-                assert_eq!(next_straight_steps, 4);
-                continue;
-            }
-
-            // // Skip if we have traveled in a straight line for more than 3 steps
-            // let prev_pos = came_from.get(&pos).unwrap();
-            // // Don't allow 180 degree turns
-            // if prev_pos == &next_pos {
-            //     continue;
-            // }
-            // let prev_pos = came_from.get(prev_pos).unwrap();
-            // let prev_pos = came_from.get(prev_pos).unwrap();
-            // match next_pos - *prev_pos {
-            //     Pos(x, 0) if x.abs() >= 4 => continue,
-            //     Pos(0, y) if y.abs() >= 4 => continue,
-            //     _ => {}
-            // }
-
-            let tentative_dist = heat_to_current_pos + get_heat(&heat_matrix, next_pos);
-            if next_pos == end {
-                return Some(tentative_dist);
-            }
-            let next_key = (next_pos, next_dir, next_straight_steps);
-            if min_heat
-                .get(&next_key)
-                .map_or(true, |&old_dist| tentative_dist < old_dist)
-            {
-                min_heat.insert(next_key, tentative_dist);
-                // came_from.insert(next_pos, pos);
-                queue.push_back(next_key);
-            }
-        }
-    }
-    panic!("No path found");
-
-    // let mut char_matrix = parse_char_matrix(input);
-    // // let mut path = vec![end];
-    // let mut current = end;
-    // while current != start {
-    //     *char_matrix.get_mut::<(Int, Int)>(current.into()).unwrap() = '#';
-    //     current = came_from[&current];
-    //     // path.push(current);
-    // }
-    // println!("\n{char_matrix}");
-    // path.reverse();
-    // println!("Path: {:?}", path);
-
-    // Some(dist[&end])
+    // No steps taken yet, so any direction is allowed at the start.
+    let start: Key = (Pos(0, 0), Direction::East, 0);
+
+    let successors = |&(pos, dir, straight_steps): &Key| {
+        DIRECTIONS
+            .into_iter()
+            .filter_map(move |next_dir| {
+                let next_pos = (pos + next_dir).in_bounds([rows, cols])?;
+
+                // Skip if we are moving back
+                if next_dir == dir.opposite() {
+                    return None;
+                }
+
+                let next_straight_steps = if dir == next_dir {
+                    straight_steps + 1
+                } else {
+                    1
+                };
+                if next_straight_steps > 3 {
+                    return None;
+                }
+
+                Some((next_pos, next_dir, next_straight_steps))
+            })
+            .map(|next_key| (next_key, get_heat(&heat_matrix, next_key.0)))
+            .collect::<Vec<_>>()
+    };
+
+    dijkstra(start, successors, |&(pos, _, _)| pos == end).cost()
 }
 
 fn get_heat(matrix: &Array2<usize>, next_pos: Pos<usize>) -> usize {
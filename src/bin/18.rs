@@ -1,7 +1,5 @@
-use std::collections::VecDeque;
-
-use advent_of_code::{Pos, DIRECTIONS};
-use hashbrown::{HashMap, HashSet};
+use advent_of_code::{astar, Pos, DIRECTIONS};
+use hashbrown::HashSet;
 
 advent_of_code::solution!(18);
 
@@ -24,36 +22,21 @@ fn solve1(input: &str, shape: usize, bytes_fallen: usize) -> usize {
 }
 
 fn solve_maze(shape: usize, corrupted_bytes: HashSet<&Pos<usize>>) -> Option<usize> {
-    let start_pos = Pos(0, 0);
     let end_pos = Pos(shape, shape);
-    let mut queue = VecDeque::<Pos<usize>>::new();
-    queue.push_back(start_pos);
-    let mut min_dist = HashMap::new();
-    min_dist.insert(Pos(0, 0), 0);
-    while let Some(pos) = queue.pop_front() {
-        let dist = min_dist[&pos];
-        for dir in DIRECTIONS {
-            let Some(next_pos) = (pos + dir).in_bounds([shape + 1, shape + 1]) else {
-                continue;
-            };
-            if corrupted_bytes.contains(&next_pos) {
-                continue;
-            }
-            let next_dist = dist + 1;
-            if next_pos == end_pos {
-                // debug_print_maze(shape, &corrupted_bytes);
-                return Some(next_dist);
-            }
-            if min_dist
-                .get(&next_pos)
-                .map_or(true, |&old_dist| next_dist < old_dist)
-            {
-                min_dist.insert(next_pos, next_dist);
-                queue.push_back(next_pos);
-            }
-        }
-    }
-    None
+    astar(
+        Pos(0, 0),
+        |&pos: &Pos<usize>| {
+            DIRECTIONS
+                .into_iter()
+                .filter_map(move |dir| (pos + dir).in_bounds([shape + 1, shape + 1]))
+                .filter(|next_pos| !corrupted_bytes.contains(next_pos))
+                .map(|next_pos| (next_pos, 1))
+                .collect::<Vec<_>>()
+        },
+        |&pos| pos == end_pos,
+        |pos| pos.manhattan_distance(end_pos),
+    )
+    .cost()
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,465 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use ndarray::{Array, Array2, Order};
+
+use crate::{get_adjacent_positions, get_surrounding_positions, Pos, DIRECTIONS};
+
+/// A two-dimensional grid of cells, indexed by `Pos(row, col)`.
+///
+/// Grids are parsed from puzzle inputs via [`FromStr`] for any cell type implementing [`FromChar`],
+/// e.g. `input.parse::<Grid<char>>()`. The underlying [`Array2`] is still available for slicing.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Array2<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid of the given size with every cell set to `value`.
+    pub fn new(rows: usize, cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: Array2::from_elem((rows, cols), value),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.nrows()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cells.ncols()
+    }
+
+    /// The number of rows and columns, which doubles as the bounds for [`Pos::in_bounds`].
+    pub fn dim(&self) -> Pos<usize> {
+        self.cells.dim().into()
+    }
+
+    /// Check if a position lies within the grid, returning the unsigned position if it does.
+    pub fn in_bounds(&self, pos: impl GridIndex) -> Option<Pos<usize>> {
+        pos.checked_index(self.dim())
+    }
+
+    pub fn get(&self, pos: impl GridIndex) -> Option<&T> {
+        let pos = self.in_bounds(pos)?;
+        self.cells.get(pos.tuple())
+    }
+
+    pub fn get_mut(&mut self, pos: impl GridIndex) -> Option<&mut T> {
+        let pos = self.in_bounds(pos)?;
+        self.cells.get_mut(pos.tuple())
+    }
+
+    /// Iterate over all cells in row-major order, together with their positions.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos<usize>, &T)> {
+        self.cells
+            .indexed_iter()
+            .map(|(idx, cell)| (Pos::from(idx), cell))
+    }
+
+    /// The position of the first cell with the given value in row-major order.
+    pub fn find(&self, value: &T) -> Option<Pos<usize>>
+    where
+        T: PartialEq,
+    {
+        self.positions_of(value).next()
+    }
+
+    /// The positions of all cells with the given value in row-major order.
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos<usize>> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    /// The positions adjacent to a given position which lie within the grid. Does not count
+    /// diagonals.
+    pub fn neighbors(&self, pos: Pos<usize>) -> impl Iterator<Item = Pos<usize>> + Clone {
+        get_adjacent_positions(pos, self.dim())
+    }
+
+    /// The eight positions surrounding a given position which lie within the grid, i.e. including
+    /// diagonals.
+    pub fn surrounding(&self, pos: Pos<usize>) -> impl Iterator<Item = Pos<usize>> + Clone {
+        get_surrounding_positions(pos, self.dim())
+    }
+
+    /// The four positions adjacent to a given position, wrapping around the edges of the grid.
+    pub fn wrapping_neighbors(&self, pos: Pos<usize>) -> impl Iterator<Item = Pos<usize>> + '_ {
+        DIRECTIONS
+            .iter()
+            .filter_map(move |dir| self.in_bounds(Wrapped(pos + dir)))
+    }
+
+    /// Like [`Grid::neighbors`], but also yields the cells.
+    pub fn neighbor_cells(&self, pos: Pos<usize>) -> impl Iterator<Item = (Pos<usize>, &T)> {
+        self.neighbors(pos).map(|pos| (pos, &self[pos]))
+    }
+
+    /// Create a grid of the same size by converting each cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.map(f),
+        }
+    }
+
+    pub fn as_array(&self) -> &Array2<T> {
+        &self.cells
+    }
+
+    pub fn as_array_mut(&mut self) -> &mut Array2<T> {
+        &mut self.cells
+    }
+
+    pub fn into_array(self) -> Array2<T> {
+        self.cells
+    }
+}
+
+impl<T> From<Array2<T>> for Grid<T> {
+    fn from(cells: Array2<T>) -> Self {
+        Self { cells }
+    }
+}
+
+impl<T, I: GridIndex> Index<I> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: I) -> &Self::Output {
+        let dim = self.dim();
+        match pos.checked_index(dim) {
+            Some(pos) => &self.cells[pos.tuple()],
+            None => panic!(
+                "position {pos:?} is out of bounds of a {}x{} grid",
+                dim.0, dim.1
+            ),
+        }
+    }
+}
+
+impl<T, I: GridIndex> IndexMut<I> for Grid<T> {
+    fn index_mut(&mut self, pos: I) -> &mut Self::Output {
+        let dim = self.dim();
+        match pos.checked_index(dim) {
+            Some(pos) => &mut self.cells[pos.tuple()],
+            None => panic!(
+                "position {pos:?} is out of bounds of a {}x{} grid",
+                dim.0, dim.1
+            ),
+        }
+    }
+}
+
+/// Prints one line per row, without separators between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.cells.outer_iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: FromChar> FromStr for Grid<T> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cells = vec![];
+        let mut cols = None;
+
+        for (row, line) in s.lines().enumerate() {
+            let len = line.chars().count();
+            match cols {
+                None => cols = Some(len),
+                Some(expected) if expected != len => {
+                    return Err(ParseGridError::UnevenRows {
+                        row,
+                        expected,
+                        found: len,
+                    });
+                }
+                Some(_) => {}
+            }
+
+            for (col, c) in line.chars().enumerate() {
+                cells.push(T::from_char(c).ok_or(ParseGridError::InvalidCell { c, row, col })?);
+            }
+        }
+
+        let shape = (s.lines().count(), cols.unwrap_or(0));
+        let cells = Array::from_vec(cells)
+            .into_shape_with_order((shape, Order::RowMajor))
+            .expect("the number of cells matches the shape");
+        Ok(Self { cells })
+    }
+}
+
+/// Positions that can index a [`Grid`]. Signed positions are checked against zero as well.
+pub trait GridIndex: Copy + std::fmt::Debug {
+    /// The unsigned position if it lies within the given bounds.
+    fn checked_index(self, bounds: Pos<usize>) -> Option<Pos<usize>>;
+}
+
+impl GridIndex for Pos<usize> {
+    fn checked_index(self, bounds: Pos<usize>) -> Option<Pos<usize>> {
+        (self.0 < bounds.0 && self.1 < bounds.1).then_some(self)
+    }
+}
+
+impl GridIndex for Pos<isize> {
+    fn checked_index(self, bounds: Pos<usize>) -> Option<Pos<usize>> {
+        self.in_bounds(bounds)
+    }
+}
+
+/// Indexes a [`Grid`] as a torus, i.e. positions outside of the grid wrap around its edges. E.g.
+/// `Wrapped(Pos(-1, 0))` is the first cell of the last row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Wrapped(pub Pos<isize>);
+
+impl GridIndex for Wrapped {
+    fn checked_index(self, bounds: Pos<usize>) -> Option<Pos<usize>> {
+        (bounds.0 > 0 && bounds.1 > 0).then(|| self.0.wrap(bounds))
+    }
+}
+
+/// Conversion of a character of the input into a grid cell. Implement it for enums of cells with
+/// [`char_enum!`](crate::char_enum).
+pub trait FromChar: Sized {
+    /// Returns `None` if the character does not describe a valid cell.
+    fn from_char(c: char) -> Option<Self>;
+}
+
+/// Conversion of a grid cell back into the character of the input, e.g. for printing.
+pub trait ToChar {
+    fn to_char(&self) -> char;
+}
+
+impl FromChar for char {
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+}
+
+impl ToChar for char {
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+/// Digits are parsed as their value, e.g. for height maps.
+macro_rules! impl_from_char_for_digits {
+    ($($t:ty),*) => {
+        $(
+            impl FromChar for $t {
+                fn from_char(c: char) -> Option<Self> {
+                    c.to_digit(10).map(|digit| digit as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_char_for_digits!(u8, u16, u32, u64, usize, i32, i64, isize);
+
+/// Declares an enum of grid cells together with the character of each variant, implementing
+/// [`FromChar`], [`ToChar`] and [`Display`] for it, e.g.
+/// `char_enum! { enum Tile { Wall = '#', Empty = '.' } }`. Attributes like derives are kept.
+#[macro_export]
+macro_rules! char_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $( $(#[$variant_meta:meta])* $variant:ident = $c:literal ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $( $(#[$variant_meta])* $variant ),*
+        }
+
+        impl $crate::FromChar for $name {
+            fn from_char(c: char) -> Option<Self> {
+                match c {
+                    $( $c => Some(Self::$variant), )*
+                    _ => None,
+                }
+            }
+        }
+
+        impl $crate::ToChar for $name {
+            fn to_char(&self) -> char {
+                match self {
+                    $( Self::$variant => $c, )*
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                use std::fmt::Write;
+                f.write_char($crate::ToChar::to_char(self))
+            }
+        }
+    };
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// A row has a different length than the first one.
+    UnevenRows {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character that does not describe a cell. Rows and columns start at 0.
+    InvalidCell { c: char, row: usize, col: usize },
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::UnevenRows {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {found} cells, expecting {expected} like the first row",
+                row + 1
+            ),
+            ParseGridError::InvalidCell { c, row, col } => write!(
+                f,
+                "invalid cell {c:?} in row {}, column {}",
+                row + 1,
+                col + 1
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, ParseGridError, Wrapped};
+    use crate::Pos;
+
+    const GRID: &str = "#.#\n.@.\n#..";
+
+    #[test]
+    fn parses_and_displays() {
+        let grid: Grid<char> = GRID.parse().unwrap();
+        assert_eq!(grid.dim(), Pos(3, 3));
+        assert_eq!(grid[Pos(1usize, 1)], '@');
+        assert_eq!(grid.to_string(), GRID);
+
+        let digits: Grid<u8> = "012\n345".parse().unwrap();
+        assert_eq!(digits.dim(), Pos(2, 3));
+        assert_eq!(digits[Pos(1usize, 2)], 5);
+    }
+
+    #[test]
+    fn rejects_invalid_grids() {
+        assert_eq!(
+            "12\n3".parse::<Grid<u8>>(),
+            Err(ParseGridError::UnevenRows {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        let error = "12\n1a".parse::<Grid<u8>>().unwrap_err();
+        assert_eq!(
+            error,
+            ParseGridError::InvalidCell {
+                c: 'a',
+                row: 1,
+                col: 1
+            }
+        );
+        assert_eq!(error.to_string(), "invalid cell 'a' in row 2, column 2");
+    }
+
+    crate::char_enum! {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        enum Tile {
+            Wall = '#',
+            Empty = '.',
+            Robot = '@',
+        }
+    }
+
+    #[test]
+    fn parses_char_enums() {
+        let grid: Grid<Tile> = GRID.parse().unwrap();
+        assert_eq!(grid[Pos(1usize, 1)], Tile::Robot);
+        assert_eq!(grid.positions_of(&Tile::Wall).count(), 3);
+        assert_eq!(grid.to_string(), GRID);
+        assert_eq!(
+            "#.\n.x".parse::<Grid<Tile>>(),
+            Err(ParseGridError::InvalidCell {
+                c: 'x',
+                row: 1,
+                col: 1
+            })
+        );
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let mut grid: Grid<char> = GRID.parse().unwrap();
+        assert_eq!(grid.get(Pos(-1isize, 0)), None);
+        assert_eq!(grid.get(Pos(0usize, 3)), None);
+        assert_eq!(grid.get(Pos(2isize, 0)), Some(&'#'));
+
+        grid[Pos(0isize, 1)] = 'O';
+        assert_eq!(grid[Pos(0usize, 1)], 'O');
+    }
+
+    #[test]
+    fn wraps_around_edges() {
+        let grid: Grid<char> = GRID.parse().unwrap();
+        assert_eq!(grid[Wrapped(Pos(-1, -1))], '.');
+        assert_eq!(grid[Wrapped(Pos(4, 3))], '.');
+        assert_eq!(grid.get(Wrapped(Pos(-3, 5))), Some(&'#'));
+        assert_eq!(
+            grid.wrapping_neighbors(Pos(0, 0)).collect::<Vec<_>>(),
+            vec![Pos(2, 0), Pos(0, 1), Pos(1, 0), Pos(0, 2)]
+        );
+    }
+
+    #[test]
+    fn finds_cells_and_neighbors() {
+        let grid: Grid<char> = GRID.parse().unwrap();
+        assert_eq!(grid.find(&'@'), Some(Pos(1, 1)));
+        assert_eq!(grid.find(&'x'), None);
+        assert_eq!(
+            grid.positions_of(&'#').collect::<Vec<_>>(),
+            vec![Pos(0, 0), Pos(0, 2), Pos(2, 0)]
+        );
+        assert_eq!(
+            grid.neighbor_cells(Pos(0, 0)).collect::<Vec<_>>(),
+            vec![(Pos(0, 1), &'.'), (Pos(1, 0), &'.')]
+        );
+        assert_eq!(
+            grid.map(|&c| c == '#').iter().filter(|(_, &b)| b).count(),
+            3
+        );
+    }
+}
@@ -1,8 +1,17 @@
 pub mod template;
-use std::{
-    fmt::Display,
-    ops::{Add, Mul, Neg, Sub},
-};
+
+mod grid;
+mod pos;
+mod pos_n;
+mod regions;
+mod search;
+mod sparse_grid;
+pub use grid::*;
+pub use pos::*;
+pub use pos_n::*;
+pub use regions::*;
+pub use search::*;
+pub use sparse_grid::*;
 
 use ndarray::{Array, Array2, Order};
 use Direction::*;
@@ -15,10 +24,32 @@ pub fn get_adjacent_positions(
     pos: impl Into<Pos<usize>>,
     bounds: impl Into<Pos<usize>>,
 ) -> impl Iterator<Item = Pos<usize>> + Clone {
-    let pos = pos.into();
-    let bounds = bounds.into();
-    DIRECTIONS
-        .iter()
+    get_positions_towards(pos.into(), bounds.into(), &DIRECTIONS)
+}
+
+/// Get the diagonally adjacent positions of a given position, if they are within the given bounds.
+pub fn get_diagonal_positions(
+    pos: impl Into<Pos<usize>>,
+    bounds: impl Into<Pos<usize>>,
+) -> impl Iterator<Item = Pos<usize>> + Clone {
+    get_positions_towards(pos.into(), bounds.into(), &DIAGONALS)
+}
+
+/// Get the eight positions surrounding a given position (i.e. the moves of a king in chess), if
+/// they are within the given bounds.
+pub fn get_surrounding_positions(
+    pos: impl Into<Pos<usize>>,
+    bounds: impl Into<Pos<usize>>,
+) -> impl Iterator<Item = Pos<usize>> + Clone {
+    get_positions_towards(pos.into(), bounds.into(), &DIRECTIONS_8)
+}
+
+fn get_positions_towards<D: Into<Pos<isize>> + Copy>(
+    pos: Pos<usize>,
+    bounds: Pos<usize>,
+    dirs: &'static [D],
+) -> impl Iterator<Item = Pos<usize>> + Clone {
+    dirs.iter()
         .filter_map(move |dir| (pos + *dir).in_bounds(bounds))
 }
 
@@ -143,159 +174,109 @@ impl Direction {
 
 pub const DIRECTIONS: [Direction; 4] = [North, East, South, West];
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Pos<T>(pub T, pub T);
-
-impl<T: Display> Display for Pos<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Pos({}, {})", self.0, self.1)
-    }
-}
-
-impl<T: std::fmt::Debug> std::fmt::Debug for Pos<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Pos({:?}, {:?})", self.0, self.1)
-    }
-}
-
-impl std::ops::AddAssign<Direction> for Pos<isize> {
-    fn add_assign(&mut self, rhs: Direction) {
-        *self = *self + rhs;
-    }
-}
-
-impl<T> Mul<T> for Pos<T>
-where
-    T: Mul<Output = T> + Copy,
-{
-    type Output = Self;
-
-    fn mul(self, rhs: T) -> Self::Output {
-        Pos(self.0 * rhs, self.1 * rhs)
-    }
-}
-
-impl<T> From<(T, T)> for Pos<T> {
-    fn from(value: (T, T)) -> Self {
-        Pos(value.0, value.1)
-    }
+/// One of the eight directions including diagonals, in clockwise order starting at north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
 }
 
-impl<T: Copy> From<[T; 2]> for Pos<T> {
-    fn from(value: [T; 2]) -> Self {
-        Pos(value[0], value[1])
+impl Direction8 {
+    /// Rotate clockwise by 45 degrees.
+    pub fn rotate_right(self) -> Direction8 {
+        self.rotate(1)
     }
-}
 
-impl<T> From<Pos<T>> for [T; 2] {
-    fn from(val: Pos<T>) -> Self {
-        [val.0, val.1]
+    /// Rotate counterclockwise by 45 degrees.
+    pub fn rotate_left(self) -> Direction8 {
+        self.rotate(7)
     }
-}
 
-impl<T> From<Pos<T>> for (T, T) {
-    fn from(val: Pos<T>) -> Self {
-        (val.0, val.1)
+    /// Rotate clockwise by 90 degrees.
+    pub fn turn_right(self) -> Direction8 {
+        self.rotate(2)
     }
-}
-
-impl<T: Add<Output = T>> Add for Pos<T> {
-    type Output = Self;
 
-    fn add(self, rhs: Pos<T>) -> Self::Output {
-        Pos(self.0 + rhs.0, self.1 + rhs.1)
+    /// Rotate counterclockwise by 90 degrees.
+    pub fn turn_left(self) -> Direction8 {
+        self.rotate(6)
     }
-}
 
-impl Pos<usize> {
-    /// Add a signed position to an unsigned position, saturating on overflow.
-    pub fn add_checked_with_bounds(
-        self,
-        rhs: impl Into<Pos<isize>>,
-        bounds: impl Into<Pos<usize>>,
-    ) -> Option<Self> {
-        let new = self + rhs;
-        new.in_bounds(bounds)
+    pub fn opposite(self) -> Direction8 {
+        self.rotate(4)
     }
 
-    pub fn add_saturating(self, rhs: impl Into<Pos<isize>>) -> Self {
-        let rhs: Pos<isize> = rhs.into();
-        Pos(
-            self.0.saturating_add_signed(rhs.0),
-            self.1.saturating_add_signed(rhs.1),
-        )
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
     }
 
-    pub fn tuple(self) -> (usize, usize) {
-        (self.0, self.1)
+    /// Rotate clockwise by a multiple of 45 degrees.
+    fn rotate(self, steps: usize) -> Direction8 {
+        DIRECTIONS_8[(self as usize + steps) % 8]
     }
 }
 
-impl Pos<isize> {
-    pub fn tuple(self) -> (usize, usize) {
-        (self.0.try_into().unwrap(), self.1.try_into().unwrap())
-    }
-}
-
-impl Pos<isize> {
-    /// Check if a position is within zero and the given bounds, returning the casted position if it
-    /// is.
-    pub fn in_bounds(self, bounds: impl Into<Pos<usize>>) -> Option<Pos<usize>> {
-        let bounds: Pos<usize> = bounds.into();
-        let in_bounds =
-            self.0 >= 0 && self.0 < bounds.0 as isize && self.1 >= 0 && self.1 < bounds.1 as isize;
-        if in_bounds {
-            Some(Pos(self.0 as usize, self.1 as usize))
-        } else {
-            None
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        match value {
+            North => Direction8::North,
+            East => Direction8::East,
+            South => Direction8::South,
+            West => Direction8::West,
         }
     }
 }
 
-impl<T: Into<Pos<isize>>> Add<T> for Pos<usize> {
-    type Output = Pos<isize>;
-
-    /// Add a signed position to an unsigned position, saturating on overflow.
-    fn add(self, rhs: T) -> Self::Output {
-        let rhs = rhs.into();
-        Pos(
-            rhs.0.wrapping_add_unsigned(self.0),
-            rhs.1.wrapping_add_unsigned(self.1),
-        )
+pub const DIRECTIONS_8: [Direction8; 8] = [
+    Direction8::North,
+    Direction8::NorthEast,
+    Direction8::East,
+    Direction8::SouthEast,
+    Direction8::South,
+    Direction8::SouthWest,
+    Direction8::West,
+    Direction8::NorthWest,
+];
+
+pub const DIAGONALS: [Direction8; 4] = [
+    Direction8::NorthEast,
+    Direction8::SouthEast,
+    Direction8::SouthWest,
+    Direction8::NorthWest,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotates_directions_8() {
+        assert_eq!(Direction8::North.rotate_right(), Direction8::NorthEast);
+        assert_eq!(Direction8::North.rotate_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::NorthEast.turn_right(), Direction8::SouthEast);
+        assert_eq!(Direction8::West.turn_left(), Direction8::South);
+        assert_eq!(Direction8::SouthWest.opposite(), Direction8::NorthEast);
+        assert!(DIAGONALS.iter().all(|dir| dir.is_diagonal()));
+        assert_eq!(Direction8::from(West), Direction8::West);
+        assert_eq!(Pos(2isize, 2) + Direction8::SouthWest, Pos(3, 1));
     }
-}
-
-impl Add<Direction> for Pos<isize> {
-    type Output = Pos<isize>;
 
-    /// Add a signed position to an unsigned position, saturating on overflow.
-    fn add(self, rhs: Direction) -> Self::Output {
-        let rhs: Pos<isize> = rhs.into();
-        self + rhs
-    }
-}
-
-impl<T: TryInto<isize, Error: std::fmt::Debug>> Sub<Pos<T>> for Pos<usize> {
-    type Output = Pos<isize>;
-
-    /// Subtract a signed position to an unsigned position, saturating on overflow.
-    fn sub(self, rhs: Pos<T>) -> Self::Output {
-        let x: isize = rhs.0.try_into().unwrap();
-        let y: isize = rhs.1.try_into().unwrap();
-        Pos(
-            x.neg().wrapping_add_unsigned(self.0),
-            y.neg().wrapping_add_unsigned(self.1),
-        )
-    }
-}
-
-impl<D: std::borrow::Borrow<Direction>> From<D> for Pos<isize> {
-    fn from(value: D) -> Self {
-        match *value.borrow() {
-            North => Pos(-1, 0),
-            East => Pos(0, 1),
-            South => Pos(1, 0),
-            West => Pos(0, -1),
-        }
+    #[test]
+    fn gets_surrounding_positions() {
+        assert_eq!(
+            get_diagonal_positions(Pos(0, 1), Pos(2, 3)).collect::<Vec<_>>(),
+            vec![Pos(1, 2), Pos(1, 0)]
+        );
+        assert_eq!(get_surrounding_positions(Pos(1, 1), Pos(3, 3)).count(), 8);
+        assert_eq!(
+            get_surrounding_positions(Pos(0, 0), Pos(3, 3)).collect::<Vec<_>>(),
+            vec![Pos(0, 1), Pos(1, 1), Pos(1, 0)]
+        );
     }
 }
@@ -1,4 +1,7 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, completions, download, inputs, read, scaffold, solve, status, time,
+};
+use advent_of_code::template::DaySet;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,15 +10,19 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        cli, cli::Shell, commands::inputs, config::config, Day, DaySet, RunOptions,
+    };
     use std::process;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            /// `None` downloads all days.
+            days: Option<DaySet>,
         },
         Read {
             day: Day,
+            part: Option<u8>,
         },
         Scaffold {
             day: Day,
@@ -23,72 +30,205 @@ mod args {
             overwrite: bool,
         },
         Solve {
-            day: Day,
-            release: bool,
+            days: DaySet,
+            options: RunOptions,
             dhat: bool,
             submit: Option<u8>,
         },
         All {
-            release: bool,
+            options: RunOptions,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            days: Option<DaySet>,
             store: bool,
+            options: RunOptions,
+        },
+        Status,
+        Inputs {
+            action: inputs::Action,
+            days: Option<DaySet>,
+        },
+        Completions {
+            shell: Shell,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Parses a required free-standing argument, naming it if it is missing.
+    fn required<T>(args: &mut pico_args::Arguments, name: &str) -> Result<T, String>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        match args.free_from_str() {
+            Err(pico_args::Error::MissingArgument) => {
+                Err(format!("missing required argument {name}"))
+            }
+            result => result.map_err(|e| e.to_string()),
+        }
+    }
+
+    fn part(s: &str) -> Result<u8, String> {
+        match s.parse() {
+            Ok(part @ (1 | 2)) => Ok(part),
+            _ => Err("expecting part 1 or 2".into()),
+        }
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+        let Some(command) = args.subcommand()? else {
+            if args.contains(["-V", "--version"]) {
+                cli::print_version();
+                process::exit(0);
+            }
+            if args.contains(["-h", "--help"]) {
+                cli::print_help();
+                process::exit(0);
+            }
+            return Err("no command specified".into());
+        };
+
+        let Some(spec) = cli::find_command(&command) else {
+            return Err(format!("unknown command `{command}`").into());
+        };
+
+        if args.contains(["-h", "--help"]) {
+            cli::print_command_help(spec);
+            process::exit(0);
+        }
+
+        // NOTE: flags that are not passed fall back to the defaults in `aoc.toml`.
+        let defaults = &config().runner;
+
+        // NOTE: free-standing arguments have to be parsed after all options.
+        let app_args = match command.as_str() {
+            "all" => AppArguments::All {
+                options: RunOptions {
+                    is_release: args.contains("--release") || defaults.release,
+                    format: args.opt_value_from_str("--format")?.or(defaults.format),
+                    part: args.opt_value_from_fn("--part", part)?,
+                    ..RunOptions::default()
+                },
             },
-            Some("time") => {
+            "time" => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let memory = args.contains("--memory") || defaults.memory;
+                let format = args.opt_value_from_str("--format")?.or(defaults.format);
+                let part = args.opt_value_from_fn("--part", part)?;
+                let variants = args.contains("--variants");
+                let days = args.opt_free_from_str()?;
+
+                if all && days.is_some() {
+                    return Err("`--all` cannot be combined with days".into());
+                }
+                // NOTE: stored timings always cover both parts of a day.
+                if store && part.is_some() {
+                    return Err("`--store` cannot be combined with `--part`".into());
+                }
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    days,
                     store,
+                    options: RunOptions {
+                        count_allocs: memory,
+                        format,
+                        part,
+                        variants,
+                        ..RunOptions::default()
+                    },
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            "download" => {
+                let all = args.contains("--all");
+                let days = args.opt_free_from_str()?;
+
+                match (all, &days) {
+                    (true, Some(_)) => return Err("`--all` cannot be combined with days".into()),
+                    (false, None) => {
+                        return Err("missing required argument [days] or `--all`".into())
+                    }
+                    _ => {}
+                }
+
+                AppArguments::Download { days }
+            }
+            "read" => AppArguments::Read {
+                part: args.opt_value_from_fn("--part", part)?,
+                day: required(&mut args, "<day>")?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+            "scaffold" => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+
+                AppArguments::Scaffold {
+                    day: required(&mut args, "<day>")?,
+                    download,
+                    overwrite,
+                }
+            }
+            "solve" => {
+                let release = args.contains("--release") || defaults.release;
+                let submit = args.opt_value_from_fn("--submit", part)?;
+                let dhat = args.contains("--dhat");
+                let memory = args.contains("--memory");
+                let format = args.opt_value_from_str("--format")?.or(defaults.format);
+                let part = args.opt_value_from_fn("--part", part)?;
+                let variants = args.contains("--variants");
+                let check_determinism = args.contains("--check-determinism");
+                let days: DaySet = required(&mut args, "<days>")?;
+
+                if dhat && memory {
+                    return Err("`--dhat` cannot be combined with `--memory`".into());
+                }
+                if dhat && submit.is_some() {
+                    return Err("`--dhat` cannot be combined with `--submit`".into());
+                }
+                let memory = memory || (defaults.memory && !dhat);
+                if days.single().is_none() && (dhat || submit.is_some()) {
+                    return Err("`--dhat` and `--submit` require a single day".into());
+                }
+                if submit.is_some() && part.is_some_and(|part| Some(part) != submit) {
+                    return Err("`--submit` and `--part` select different parts".into());
+                }
+
+                AppArguments::Solve {
+                    days,
+                    options: RunOptions {
+                        is_timed: false,
+                        is_release: release,
+                        count_allocs: memory,
+                        format,
+                        part,
+                        variants,
+                        check_determinism,
+                    },
+                    dhat,
+                    submit,
+                }
+            }
+            "status" => AppArguments::Status,
+            "inputs" => AppArguments::Inputs {
+                action: required(&mut args, "<action>")?,
+                days: args.opt_free_from_str()?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            "completions" => AppArguments::Completions {
+                shell: required(&mut args, "<shell>")?,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            "today" => AppArguments::Today,
+            _ => unreachable!("command `{command}` is listed in cli::COMMANDS but not parsed"),
         };
 
         let remaining = args.finish();
         if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+            let remaining: Vec<_> = remaining.iter().map(|x| x.to_string_lossy()).collect();
+            return Err(format!("unexpected argument(s): {}", remaining.join(" ")).into());
         }
 
         Ok(app_args)
@@ -99,13 +239,23 @@ fn main() {
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
+            eprintln!();
+            eprintln!("For more information, try `--help`.");
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All { options } => all::handle(&options),
+            AppArguments::Time {
+                days,
+                all,
+                store,
+                options,
+            } => time::handle(days, all, store, &options),
+            AppArguments::Download { days } => match days.as_ref().and_then(DaySet::single) {
+                Some(day) => download::handle(day),
+                None => download::handle_many(days),
+            },
+            AppArguments::Read { day, part } => read::handle(day, part),
             AppArguments::Scaffold {
                 day,
                 download,
@@ -117,18 +267,21 @@ fn main() {
                 }
             }
             AppArguments::Solve {
-                day,
-                release,
+                days,
+                options,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+            } => solve::handle(days, &options, dhat, submit),
+            AppArguments::Status => status::handle(),
+            AppArguments::Inputs { action, days } => inputs::handle(action, days),
+            AppArguments::Completions { shell } => completions::handle(shell),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
                         scaffold::handle(day, false);
                         download::handle(day);
-                        read::handle(day)
+                        read::handle(day, None)
                     }
                     None => {
                         eprintln!(
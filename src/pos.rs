@@ -0,0 +1,417 @@
+use std::{
+    borrow::Borrow,
+    fmt::Display,
+    ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign},
+};
+
+use num::{traits::Euclid, Signed};
+
+use crate::{Direction, Direction8};
+
+/// A position or an offset on a grid, as `Pos(row, col)`.
+///
+/// All operators work component-wise on positions of the same type, e.g. `Pos<usize>` minus
+/// `Pos<usize>` is a `Pos<usize>` and panics on underflow in debug builds. Use [`Pos::try_cast`] to
+/// convert between signed and unsigned positions. The one exception is offsetting an unsigned
+/// position by a signed offset (e.g. a [`Direction`]), which yields a signed position that can be
+/// checked with [`Pos::in_bounds`].
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos<T>(pub T, pub T);
+
+impl<T> Pos<T> {
+    /// Apply a function to both components.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Pos<U> {
+        Pos(f(self.0), f(self.1))
+    }
+
+    pub fn tuple(self) -> (T, T) {
+        (self.0, self.1)
+    }
+
+    /// Convert the components to another integer type, e.g. from signed to unsigned. Returns
+    /// `None` if a component does not fit into the new type.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Pos<U>> {
+        Some(Pos(U::try_from(self.0).ok()?, U::try_from(self.1).ok()?))
+    }
+
+    /// Convert the components to a wider type, e.g. from `u8` to `usize` or `i32` to `i64`.
+    pub fn cast<U: From<T>>(self) -> Pos<U> {
+        self.map(U::from)
+    }
+}
+
+impl<T> Pos<T>
+where
+    usize: TryFrom<T>,
+{
+    /// Check if a position is within zero and the given bounds, returning the unsigned position if
+    /// it is.
+    pub fn in_bounds(self, bounds: impl Into<Pos<usize>>) -> Option<Pos<usize>> {
+        let bounds: Pos<usize> = bounds.into();
+        let pos: Pos<usize> = self.try_cast()?;
+        (pos.0 < bounds.0 && pos.1 < bounds.1).then_some(pos)
+    }
+}
+
+impl<T: Euclid + TryFrom<usize>> Pos<T>
+where
+    usize: TryFrom<T>,
+{
+    /// Wrap a position around the edges of the given bounds, e.g. for puzzles on a torus where
+    /// leaving the grid on one side enters it on the opposite one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bounds do not fit into `T` or are zero.
+    pub fn wrap(self, bounds: impl Into<Pos<usize>>) -> Pos<usize> {
+        let bounds: Pos<T> = bounds
+            .into()
+            .try_cast()
+            .expect("bounds should fit into the type of the position");
+        Pos(self.0.rem_euclid(&bounds.0), self.1.rem_euclid(&bounds.1))
+            .try_cast()
+            .expect("wrapped positions are not negative")
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Pos<T> {
+    /// The number of orthogonal steps between two positions.
+    pub fn manhattan_distance(self, other: Self) -> T {
+        abs_diff(self.0, other.0) + abs_diff(self.1, other.1)
+    }
+
+    /// The number of steps between two positions if diagonal steps are allowed, like a king in
+    /// chess.
+    pub fn chebyshev_distance(self, other: Self) -> T {
+        let rows = abs_diff(self.0, other.0);
+        let cols = abs_diff(self.1, other.1);
+        if rows > cols {
+            rows
+        } else {
+            cols
+        }
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Pos<T> {
+    /// Rotate an offset clockwise by 90 degrees around the origin, e.g. north to east.
+    pub fn rotate_right(self) -> Self {
+        Pos(self.1, -self.0)
+    }
+
+    /// Rotate an offset counterclockwise by 90 degrees around the origin, e.g. north to west.
+    pub fn rotate_left(self) -> Self {
+        Pos(-self.1, self.0)
+    }
+}
+
+impl<T: Signed> Pos<T> {
+    /// The component-wise absolute value.
+    pub fn abs(self) -> Self {
+        self.map(|x| x.abs())
+    }
+
+    /// The component-wise sign, e.g. to step from one position towards another.
+    pub fn signum(self) -> Self {
+        self.map(|x| x.signum())
+    }
+}
+
+impl Pos<usize> {
+    /// Add a signed position to an unsigned position, returning `None` if the result is negative
+    /// or outside the given bounds.
+    pub fn add_checked_with_bounds(
+        self,
+        rhs: impl Into<Pos<isize>>,
+        bounds: impl Into<Pos<usize>>,
+    ) -> Option<Self> {
+        let new = self + rhs;
+        new.in_bounds(bounds)
+    }
+
+    /// Add a signed position to an unsigned position, saturating on overflow.
+    pub fn add_saturating(self, rhs: impl Into<Pos<isize>>) -> Self {
+        let rhs: Pos<isize> = rhs.into();
+        Pos(
+            self.0.saturating_add_signed(rhs.0),
+            self.1.saturating_add_signed(rhs.1),
+        )
+    }
+}
+
+fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T: Display> Display for Pos<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Pos({}, {})", self.0, self.1)
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Pos<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Pos({:?}, {:?})", self.0, self.1)
+    }
+}
+
+// --------------------------------------------------------------------------
+
+impl<T> From<(T, T)> for Pos<T> {
+    fn from(value: (T, T)) -> Self {
+        Pos(value.0, value.1)
+    }
+}
+
+impl<T: Copy> From<[T; 2]> for Pos<T> {
+    fn from(value: [T; 2]) -> Self {
+        Pos(value[0], value[1])
+    }
+}
+
+impl<T> From<Pos<T>> for [T; 2] {
+    fn from(val: Pos<T>) -> Self {
+        [val.0, val.1]
+    }
+}
+
+impl<T> From<Pos<T>> for (T, T) {
+    fn from(val: Pos<T>) -> Self {
+        (val.0, val.1)
+    }
+}
+
+impl<D: Borrow<Direction>> From<D> for Pos<isize> {
+    fn from(value: D) -> Self {
+        match *value.borrow() {
+            Direction::North => Pos(-1, 0),
+            Direction::East => Pos(0, 1),
+            Direction::South => Pos(1, 0),
+            Direction::West => Pos(0, -1),
+        }
+    }
+}
+
+impl From<Direction8> for Pos<isize> {
+    fn from(value: Direction8) -> Self {
+        match value {
+            Direction8::North => Pos(-1, 0),
+            Direction8::NorthEast => Pos(-1, 1),
+            Direction8::East => Pos(0, 1),
+            Direction8::SouthEast => Pos(1, 1),
+            Direction8::South => Pos(1, 0),
+            Direction8::SouthWest => Pos(1, -1),
+            Direction8::West => Pos(0, -1),
+            Direction8::NorthWest => Pos(-1, -1),
+        }
+    }
+}
+
+// --------------------------------------------------------------------------
+
+impl<T: Add<Output = T>> Add for Pos<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Pos<T>) -> Self::Output {
+        Pos(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Pos<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Pos<T>) -> Self::Output {
+        Pos(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Pos<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Pos(-self.0, -self.1)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Pos<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Pos(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<T> for Pos<T> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Pos(self.0 / rhs, self.1 / rhs)
+    }
+}
+
+impl<T: Rem<Output = T> + Copy> Rem<T> for Pos<T> {
+    type Output = Self;
+
+    fn rem(self, rhs: T) -> Self::Output {
+        Pos(self.0 % rhs, self.1 % rhs)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Pos<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Pos<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+    }
+}
+
+impl<T: MulAssign + Copy> MulAssign<T> for Pos<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.0 *= rhs;
+        self.1 *= rhs;
+    }
+}
+
+impl<T: Into<Pos<isize>>> Add<T> for Pos<usize> {
+    type Output = Pos<isize>;
+
+    /// Offset an unsigned position, e.g. by a [`Direction`]. The result may be negative.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the position does not fit into an `isize`.
+    fn add(self, rhs: T) -> Self::Output {
+        let pos: Pos<isize> = self.try_cast().expect("position should fit into an isize");
+        pos + rhs.into()
+    }
+}
+
+impl Add<Direction> for Pos<isize> {
+    type Output = Pos<isize>;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        let rhs: Pos<isize> = rhs.into();
+        self + rhs
+    }
+}
+
+impl Add<Direction8> for Pos<isize> {
+    type Output = Pos<isize>;
+
+    fn add(self, rhs: Direction8) -> Self::Output {
+        let rhs: Pos<isize> = rhs.into();
+        self + rhs
+    }
+}
+
+impl AddAssign<Direction> for Pos<isize> {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = *self + rhs;
+    }
+}
+
+impl AddAssign<Direction8> for Pos<isize> {
+    fn add_assign(&mut self, rhs: Direction8) {
+        *self = *self + rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pos;
+    use crate::{Direction, Direction8};
+
+    #[test]
+    fn adds_and_subtracts() {
+        assert_eq!(Pos(1, 2) + Pos(3, 4), Pos(4, 6));
+        assert_eq!(Pos(5usize, 4) - Pos(3, 4), Pos(2, 0));
+        assert_eq!(Pos(1isize, 2) - Pos(3, 4), Pos(-2, -2));
+        assert_eq!(-Pos(1, -2), Pos(-1, 2));
+
+        let mut pos = Pos(1, 1);
+        pos += Pos(2, 3);
+        assert_eq!(pos, Pos(3, 4));
+        pos -= Pos(1, 1);
+        assert_eq!(pos, Pos(2, 3));
+    }
+
+    #[test]
+    fn scales() {
+        assert_eq!(Pos(1, -2) * 3, Pos(3, -6));
+        assert_eq!(Pos(7, -8) / 2, Pos(3, -4));
+        assert_eq!(Pos(7, 8) % 3, Pos(1, 2));
+
+        let mut pos = Pos(2usize, 3);
+        pos *= 2;
+        assert_eq!(pos, Pos(4, 6));
+    }
+
+    #[test]
+    fn offsets_by_directions() {
+        assert_eq!(Pos(0usize, 0) + Direction::North, Pos(-1, 0));
+        assert_eq!(Pos(1usize, 1) + Pos(-1isize, 2), Pos(0, 3));
+        assert_eq!(Pos(0isize, 0) + Direction::East, Pos(0, 1));
+        assert_eq!(Pos(0isize, 0) + Direction8::SouthWest, Pos(1, -1));
+
+        let mut pos = Pos(0isize, 0);
+        pos += Direction::South;
+        pos += Direction8::NorthEast;
+        assert_eq!(pos, Pos(0, 1));
+    }
+
+    #[test]
+    fn converts_between_signed_and_unsigned() {
+        assert_eq!(Pos(1isize, 2).try_cast::<usize>(), Some(Pos(1, 2)));
+        assert_eq!(Pos(-1isize, 2).try_cast::<usize>(), None);
+        assert_eq!(Pos(1usize, 2).try_cast::<isize>(), Some(Pos(1, 2)));
+        assert_eq!(Pos(1u8, 2).cast::<usize>(), Pos(1, 2));
+        assert_eq!(Pos(1, 2).map(|x| x * 10), Pos(10, 20));
+        assert_eq!(Pos(1, 2).tuple(), (1, 2));
+    }
+
+    #[test]
+    fn checks_bounds() {
+        assert_eq!(Pos(1isize, 2).in_bounds((2, 3)), Some(Pos(1, 2)));
+        assert_eq!(Pos(-1isize, 2).in_bounds((2, 3)), None);
+        assert_eq!(Pos(2usize, 0).in_bounds((2, 3)), None);
+        assert_eq!(
+            Pos(0usize, 0).add_checked_with_bounds(Direction::West, (2, 2)),
+            None
+        );
+        assert_eq!(Pos(0usize, 0).add_saturating(Direction::West), Pos(0, 0));
+    }
+
+    #[test]
+    fn wraps_around_bounds() {
+        assert_eq!(Pos(-1isize, 7).wrap((5, 5)), Pos(4, 2));
+        assert_eq!(Pos(-11i64, 3).wrap((5, 5)), Pos(4, 3));
+        assert_eq!(Pos(12usize, 0).wrap((5, 5)), Pos(2, 0));
+    }
+
+    #[test]
+    fn measures_distances() {
+        assert_eq!(Pos(1usize, 5).manhattan_distance(Pos(4, 2)), 6);
+        assert_eq!(Pos(-1, 5).manhattan_distance(Pos(4, 2)), 8);
+        assert_eq!(Pos(1usize, 5).chebyshev_distance(Pos(4, 2)), 3);
+        assert_eq!(Pos(-3, -4).abs(), Pos(3, 4));
+        assert_eq!(Pos(-3, 0).signum(), Pos(-1, 0));
+    }
+
+    #[test]
+    fn rotates() {
+        let north: Pos<isize> = Direction::North.into();
+        assert_eq!(north.rotate_right(), Direction::East.into());
+        assert_eq!(north.rotate_left(), Direction::West.into());
+        assert_eq!(Pos(1, 2).rotate_right().rotate_left(), Pos(1, 2));
+    }
+}
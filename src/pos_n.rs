@@ -0,0 +1,371 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, AddAssign, Div, Index, IndexMut, Mul, MulAssign, Neg, Rem, Sub, SubAssign},
+};
+
+use num::Signed;
+
+use crate::Pos;
+
+/// A position or an offset with `N` components, e.g. `PosN([x, y, z])`. Supports the same
+/// component-wise operators as [`Pos`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PosN<T, const N: usize>(pub [T; N]);
+
+/// A three-dimensional position, e.g. `Pos3::from((x, y, z))`.
+pub type Pos3<T> = PosN<T, 3>;
+
+impl<T, const N: usize> PosN<T, N> {
+    /// Apply a function to every component.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> PosN<U, N> {
+        PosN(self.0.map(f))
+    }
+
+    /// Convert the components to another integer type, e.g. from signed to unsigned. Returns
+    /// `None` if a component does not fit into the new type.
+    pub fn try_cast<U: TryFrom<T> + Copy + Default>(self) -> Option<PosN<U, N>> {
+        let mut result = [U::default(); N];
+        for (to, from) in result.iter_mut().zip(self.0) {
+            *to = U::try_from(from).ok()?;
+        }
+        Some(PosN(result))
+    }
+}
+
+impl<T: Copy, const N: usize> PosN<T, N>
+where
+    usize: TryFrom<T>,
+{
+    /// Check if a position is within zero and the given bounds, returning the unsigned position if
+    /// it is.
+    pub fn in_bounds(self, bounds: PosN<usize, N>) -> Option<PosN<usize, N>> {
+        let pos: PosN<usize, N> = self.try_cast()?;
+        pos.0
+            .iter()
+            .zip(bounds.0)
+            .all(|(&x, bound)| x < bound)
+            .then_some(pos)
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>, const N: usize> PosN<T, N> {
+    /// The number of orthogonal steps between two positions.
+    pub fn manhattan_distance(self, other: Self) -> T {
+        self.abs_diffs(other)
+            .reduce(|a, b| a + b)
+            .expect("positions have at least one component")
+    }
+
+    /// The number of steps between two positions if diagonal steps are allowed.
+    pub fn chebyshev_distance(self, other: Self) -> T {
+        self.abs_diffs(other)
+            .reduce(|a, b| if a > b { a } else { b })
+            .expect("positions have at least one component")
+    }
+
+    fn abs_diffs(self, other: Self) -> impl Iterator<Item = T> {
+        self.0
+            .into_iter()
+            .zip(other.0)
+            .map(|(a, b)| if a > b { a - b } else { b - a })
+    }
+}
+
+impl<T: Signed + Copy, const N: usize> PosN<T, N> {
+    /// The `2 * N` positions that differ by one in a single component, e.g. 6 in three dimensions.
+    pub fn orthogonal_neighbors(self) -> impl Iterator<Item = Self> {
+        (0..2 * N).map(move |i| {
+            let mut pos = self;
+            if i % 2 == 0 {
+                pos.0[i / 2] = pos.0[i / 2] - T::one();
+            } else {
+                pos.0[i / 2] = pos.0[i / 2] + T::one();
+            }
+            pos
+        })
+    }
+
+    /// The `3^N - 1` positions that differ by at most one in every component, e.g. 26 in three
+    /// dimensions.
+    pub fn surrounding(self) -> impl Iterator<Item = Self> {
+        let count = 3usize.pow(N as u32);
+        (0..count)
+            // NOTE: the middle index has all digits equal to 1, i.e. it is the position itself.
+            .filter(move |&i| i != count / 2)
+            .map(move |mut i| {
+                let mut pos = self;
+                for x in pos.0.iter_mut() {
+                    match i % 3 {
+                        0 => *x = *x - T::one(),
+                        2 => *x = *x + T::one(),
+                        _ => {}
+                    }
+                    i /= 3;
+                }
+                pos
+            })
+    }
+
+    /// The component-wise absolute value.
+    pub fn abs(self) -> Self {
+        self.map(|x| x.abs())
+    }
+
+    /// The component-wise sign, e.g. to step from one position towards another.
+    pub fn signum(self) -> Self {
+        self.map(|x| x.signum())
+    }
+}
+
+impl<const N: usize> PosN<usize, N> {
+    /// Like [`PosN::orthogonal_neighbors`], but only yields positions within the given bounds.
+    pub fn orthogonal_neighbors_in(
+        self,
+        bounds: PosN<usize, N>,
+    ) -> impl Iterator<Item = PosN<usize, N>> {
+        self.signed()
+            .orthogonal_neighbors()
+            .filter_map(move |pos| pos.in_bounds(bounds))
+    }
+
+    /// Like [`PosN::surrounding`], but only yields positions within the given bounds.
+    pub fn surrounding_in(self, bounds: PosN<usize, N>) -> impl Iterator<Item = PosN<usize, N>> {
+        self.signed()
+            .surrounding()
+            .filter_map(move |pos| pos.in_bounds(bounds))
+    }
+
+    fn signed(self) -> PosN<isize, N> {
+        self.try_cast().expect("position should fit into an isize")
+    }
+}
+
+fn fmt_components<T>(
+    components: &[T],
+    f: &mut std::fmt::Formatter<'_>,
+    fmt: impl Fn(&T, &mut std::fmt::Formatter<'_>) -> std::fmt::Result,
+) -> std::fmt::Result {
+    write!(f, "Pos(")?;
+    for (i, x) in components.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        fmt(x, f)?;
+    }
+    write!(f, ")")
+}
+
+impl<T: Display, const N: usize> Display for PosN<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_components(&self.0, f, |x, f| write!(f, "{x}"))
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for PosN<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_components(&self.0, f, |x, f| write!(f, "{x:?}"))
+    }
+}
+
+impl<T: Default, const N: usize> Default for PosN<T, N> {
+    fn default() -> Self {
+        PosN(std::array::from_fn(|_| T::default()))
+    }
+}
+
+// --------------------------------------------------------------------------
+
+impl<T, const N: usize> From<[T; N]> for PosN<T, N> {
+    fn from(value: [T; N]) -> Self {
+        PosN(value)
+    }
+}
+
+impl<T, const N: usize> From<PosN<T, N>> for [T; N] {
+    fn from(value: PosN<T, N>) -> Self {
+        value.0
+    }
+}
+
+impl<T> From<(T, T, T)> for Pos3<T> {
+    fn from(value: (T, T, T)) -> Self {
+        PosN([value.0, value.1, value.2])
+    }
+}
+
+impl<T> From<Pos<T>> for PosN<T, 2> {
+    fn from(value: Pos<T>) -> Self {
+        PosN([value.0, value.1])
+    }
+}
+
+impl<T: Copy> From<PosN<T, 2>> for Pos<T> {
+    fn from(value: PosN<T, 2>) -> Self {
+        Pos(value.0[0], value.0[1])
+    }
+}
+
+impl<T, const N: usize> Index<usize> for PosN<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for PosN<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+// --------------------------------------------------------------------------
+
+impl<T: Add<Output = T> + Copy, const N: usize> Add for PosN<T, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        PosN(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<T: Sub<Output = T> + Copy, const N: usize> Sub for PosN<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        PosN(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<T: Neg<Output = T>, const N: usize> Neg for PosN<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(|x| -x)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy, const N: usize> Mul<T> for PosN<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        self.map(|x| x * rhs)
+    }
+}
+
+impl<T: Div<Output = T> + Copy, const N: usize> Div<T> for PosN<T, N> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self::Output {
+        self.map(|x| x / rhs)
+    }
+}
+
+impl<T: Rem<Output = T> + Copy, const N: usize> Rem<T> for PosN<T, N> {
+    type Output = Self;
+
+    fn rem(self, rhs: T) -> Self::Output {
+        self.map(|x| x % rhs)
+    }
+}
+
+impl<T: AddAssign, const N: usize> AddAssign for PosN<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (x, y) in self.0.iter_mut().zip(rhs.0) {
+            *x += y;
+        }
+    }
+}
+
+impl<T: SubAssign, const N: usize> SubAssign for PosN<T, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (x, y) in self.0.iter_mut().zip(rhs.0) {
+            *x -= y;
+        }
+    }
+}
+
+impl<T: MulAssign + Copy, const N: usize> MulAssign<T> for PosN<T, N> {
+    fn mul_assign(&mut self, rhs: T) {
+        for x in self.0.iter_mut() {
+            *x *= rhs;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pos3, PosN};
+    use crate::Pos;
+
+    #[test]
+    fn applies_operators() {
+        let a = Pos3::from((1, 2, 3));
+        let b = PosN([4, 5, 6]);
+        assert_eq!(a + b, PosN([5, 7, 9]));
+        assert_eq!(a - b, PosN([-3, -3, -3]));
+        assert_eq!(-a, PosN([-1, -2, -3]));
+        assert_eq!(b * 2, PosN([8, 10, 12]));
+        assert_eq!(b / 2, PosN([2, 2, 3]));
+        assert_eq!(b % 4, PosN([0, 1, 2]));
+
+        let mut c = a;
+        c += b;
+        c -= PosN([1, 1, 1]);
+        c *= 2;
+        assert_eq!(c, PosN([8, 12, 16]));
+        assert_eq!(c[2], 16);
+    }
+
+    #[test]
+    fn converts() {
+        assert_eq!(
+            PosN([1isize, 2, 3]).try_cast::<usize>(),
+            Some(PosN([1, 2, 3]))
+        );
+        assert_eq!(PosN([1isize, -2, 3]).try_cast::<usize>(), None);
+        assert_eq!(PosN::from(Pos(1, 2)), PosN([1, 2]));
+        assert_eq!(Pos::from(PosN([1, 2])), Pos(1, 2));
+        assert_eq!(format!("{:?}", Pos3::from((1, 2, 3))), "Pos(1, 2, 3)");
+        assert_eq!(Pos3::<u8>::default(), PosN([0, 0, 0]));
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let bounds = PosN([2, 2, 2]);
+        assert_eq!(
+            PosN([1isize, 0, 1]).in_bounds(bounds),
+            Some(PosN([1, 0, 1]))
+        );
+        assert_eq!(PosN([1isize, -1, 1]).in_bounds(bounds), None);
+        assert_eq!(PosN([1usize, 2, 1]).in_bounds(bounds), None);
+    }
+
+    #[test]
+    fn finds_neighbors() {
+        let origin = Pos3::from((0isize, 0, 0));
+        assert_eq!(origin.orthogonal_neighbors().count(), 6);
+        assert!(origin
+            .orthogonal_neighbors()
+            .all(|pos| pos.manhattan_distance(origin) == 1));
+        assert_eq!(origin.surrounding().count(), 26);
+        assert!(origin
+            .surrounding()
+            .all(|pos| pos.chebyshev_distance(origin) == 1));
+
+        let corner = PosN([0usize, 0, 0]);
+        assert_eq!(corner.orthogonal_neighbors_in(PosN([3, 3, 3])).count(), 3);
+        assert_eq!(corner.surrounding_in(PosN([3, 3, 3])).count(), 7);
+        assert_eq!(PosN([5isize, 5]).surrounding().count(), 8);
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = PosN([1usize, 5, 2]);
+        let b = PosN([4, 2, 2]);
+        assert_eq!(a.manhattan_distance(b), 6);
+        assert_eq!(a.chebyshev_distance(b), 3);
+        assert_eq!(PosN([-3, 4, 0]).abs(), PosN([3, 4, 0]));
+        assert_eq!(PosN([-3, 4, 0]).signum(), PosN([-1, 1, 0]));
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::{Direction, Grid, Pos, DIRECTIONS};
+
+/// A group of orthogonally connected cells of a grid, as found by [`flood_fill`] or
+/// [`label_regions`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    cells: HashSet<Pos<usize>>,
+}
+
+impl Region {
+    pub fn contains(&self, pos: Pos<usize>) -> bool {
+        self.cells.contains(&pos)
+    }
+
+    /// Iterate over all cells of the region in arbitrary order.
+    pub fn cells(&self) -> impl Iterator<Item = Pos<usize>> + '_ {
+        self.cells.iter().copied()
+    }
+
+    /// The number of cells in the region.
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of cell edges that border a cell outside the region (or the edge of the grid).
+    pub fn perimeter(&self) -> usize {
+        self.cells()
+            .map(|pos| {
+                DIRECTIONS
+                    .iter()
+                    .filter(|&&dir| !self.contains_towards(pos, dir))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The number of straight sides of the region's outline, including the outlines of holes.
+    pub fn sides(&self) -> usize {
+        // NOTE: a polygon has as many sides as corners. Every corner of a cell is a corner of the
+        // outline if both of the cell's neighbors towards it are outside the region (convex), or
+        // if both are inside, but the diagonal neighbor between them is not (concave).
+        self.cells()
+            .map(|pos| {
+                DIRECTIONS
+                    .iter()
+                    .filter(|&&dir| {
+                        let side = dir.turn_right();
+                        let (a, b) = (
+                            self.contains_towards(pos, dir),
+                            self.contains_towards(pos, side),
+                        );
+                        let diagonal = (pos + dir + side)
+                            .try_cast()
+                            .is_some_and(|pos| self.contains(pos));
+                        (!a && !b) || (a && b && !diagonal)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The top left and bottom right corner of the bounding box of the region, inclusive.
+    pub fn bounding_box(&self) -> (Pos<usize>, Pos<usize>) {
+        let min = self.cells().reduce(|a, b| Pos(a.0.min(b.0), a.1.min(b.1)));
+        let max = self.cells().reduce(|a, b| Pos(a.0.max(b.0), a.1.max(b.1)));
+        (min.unwrap(), max.unwrap())
+    }
+
+    /// The cells of the region that have an orthogonal neighbor outside of it, in arbitrary order.
+    pub fn boundary(&self) -> impl Iterator<Item = Pos<usize>> + '_ {
+        self.cells().filter(|&pos| {
+            DIRECTIONS
+                .iter()
+                .any(|&dir| !self.contains_towards(pos, dir))
+        })
+    }
+
+    fn contains_towards(&self, pos: Pos<usize>, dir: Direction) -> bool {
+        (pos + dir).try_cast().is_some_and(|pos| self.contains(pos))
+    }
+}
+
+/// Find the region of a grid containing the given position. Neighboring cells belong to the same
+/// region if `same_region` returns true for their values, which should be an equivalence relation
+/// like `==`.
+pub fn flood_fill<T>(
+    grid: &Grid<T>,
+    start: Pos<usize>,
+    mut same_region: impl FnMut(&T, &T) -> bool,
+) -> Region {
+    let mut cells = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(pos) = queue.pop_front() {
+        for next in grid.neighbors(pos) {
+            if !cells.contains(&next) && same_region(&grid[pos], &grid[next]) {
+                cells.insert(next);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    Region { cells }
+}
+
+/// Split a grid into regions, see [`flood_fill`]. Returns the index of the region of every cell
+/// and the regions themselves, ordered by their top left cell.
+pub fn label_regions<T>(
+    grid: &Grid<T>,
+    mut same_region: impl FnMut(&T, &T) -> bool,
+) -> (Grid<usize>, Vec<Region>) {
+    let mut labels = Grid::new(grid.rows(), grid.cols(), usize::MAX);
+    let mut regions = Vec::new();
+
+    for (pos, _) in grid.iter() {
+        if labels[pos] != usize::MAX {
+            continue;
+        }
+        let region = flood_fill(grid, pos, &mut same_region);
+        for cell in region.cells() {
+            labels[cell] = regions.len();
+        }
+        regions.push(region);
+    }
+
+    (labels, regions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{flood_fill, label_regions};
+    use crate::{Grid, Pos};
+
+    #[test]
+    fn measures_regions() {
+        let grid: Grid<char> = "AAAA\nBBCD\nBBCC\nEEEC".parse().unwrap();
+        let (labels, regions) = label_regions(&grid, |a, b| a == b);
+        assert_eq!(regions.len(), 5);
+        assert_eq!(labels.to_string(), "0000\n1123\n1122\n4442");
+
+        let measures = regions
+            .iter()
+            .map(|region| (region.area(), region.perimeter(), region.sides()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            measures,
+            vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+
+        let c = &regions[2];
+        assert_eq!(c.bounding_box(), (Pos(1, 2), Pos(3, 3)));
+        assert_eq!(c.boundary().count(), 4);
+    }
+
+    #[test]
+    fn counts_sides_of_holes() {
+        let grid: Grid<char> = "AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA"
+            .parse()
+            .unwrap();
+        let region = flood_fill(&grid, Pos(0, 0), |a, b| a == b);
+        assert_eq!(region.area(), 28);
+        assert_eq!(region.sides(), 12);
+        assert!(region.contains(Pos(3, 3)));
+        assert!(!region.contains(Pos(3, 2)));
+
+        // Diagonally touching cells don't connect regions, and inner cells are not on the boundary.
+        let region = flood_fill(&grid, Pos(1, 3), |a, b| a == b);
+        assert_eq!(region.area(), 4);
+        assert_eq!(region.boundary().count(), 4);
+        let region = flood_fill(&Grid::new(3, 3, 'A'), Pos(1, 1), |a, b| a == b);
+        assert_eq!(region.boundary().count(), 8);
+    }
+}
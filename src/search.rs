@@ -0,0 +1,625 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use ndarray::Array2;
+use num::{BigUint, One, Zero};
+
+use crate::{Grid, Pos};
+
+/// The outcome of a search: the cost of every reached state, how it was reached and which goal was
+/// found, if any.
+///
+/// Searches stop as soon as a goal is found. To explore every reachable state, pass a goal
+/// predicate that is never true, e.g. `|_| false`.
+#[derive(Clone, Debug)]
+pub struct SearchResult<S, C> {
+    /// The lowest known cost of every reached state. Final for all states that were expanded.
+    pub distances: HashMap<S, C>,
+    /// The state each state was reached from on its cheapest known path. The start has none.
+    pub predecessors: HashMap<S, S>,
+    /// The first goal that was found.
+    pub goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> SearchResult<S, C> {
+    /// The cost of the cheapest path to the goal.
+    pub fn cost(&self) -> Option<C> {
+        self.distance(self.goal.as_ref()?)
+    }
+
+    /// The cheapest path from the start to the goal, including both.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.distances.get(state).copied()
+    }
+
+    /// The cheapest known path from the start to a reached state, including both.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.distances.contains_key(state) {
+            return None;
+        }
+
+        let mut path = vec![state.clone()];
+        let mut current = state;
+        while let Some(previous) = self.predecessors.get(current) {
+            path.push(previous.clone());
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search where every step costs 1.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = SearchResult {
+        distances: HashMap::from([(start.clone(), 0)]),
+        predecessors: HashMap::new(),
+        goal: None,
+    };
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+
+        let next_dist = result.distances[&state] + 1;
+        for next in successors(&state) {
+            if result.distances.contains_key(&next) {
+                continue;
+            }
+            result.distances.insert(next.clone(), next_dist);
+            result.predecessors.insert(next.clone(), state.clone());
+            queue.push_back(next);
+        }
+    }
+
+    result
+}
+
+/// Dijkstra's algorithm for successors with non-negative costs.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, is_goal, |_| C::zero())
+}
+
+/// A* search for successors with non-negative costs. The heuristic estimates the remaining cost
+/// to a goal and must never overestimate it, otherwise the found path might not be the cheapest.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+    mut heuristic: impl FnMut(&S) -> C,
+) -> SearchResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut result = SearchResult {
+        distances: HashMap::from([(start.clone(), C::zero())]),
+        predecessors: HashMap::new(),
+        goal: None,
+    };
+
+    // NOTE: states are stored separately, so that they don't need to implement `Ord`.
+    let mut states = vec![start];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&states[0]), C::zero(), 0))]);
+
+    while let Some(Reverse((_, cost, id))) = heap.pop() {
+        let state = states[id].clone();
+        // Skip outdated entries of states that were reached more cheaply in the meantime.
+        if result.distances[&state] < cost {
+            continue;
+        }
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+            if result
+                .distances
+                .get(&next)
+                .is_some_and(|&known| known <= next_cost)
+            {
+                continue;
+            }
+
+            result.distances.insert(next.clone(), next_cost);
+            result.predecessors.insert(next.clone(), state.clone());
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                states.len(),
+            )));
+            states.push(next);
+        }
+    }
+
+    result
+}
+
+/// The outcome of a search that keeps every optimal way of reaching a state, as returned by
+/// [`dijkstra_all`] and [`bfs_all`].
+///
+/// The predecessors of all reached states form a directed acyclic graph of optimal paths, which
+/// can be used to count or enumerate the paths to the goals, or to find the states on any of them.
+#[derive(Clone, Debug)]
+pub struct AllPathsResult<S, C> {
+    pub start: S,
+    /// The lowest known cost of every reached state. Final for all states that were expanded.
+    pub distances: HashMap<S, C>,
+    /// Every state each state was reached from on one of its cheapest known paths. The start has
+    /// none.
+    pub predecessors: HashMap<S, Vec<S>>,
+    /// All goals that can be reached at the lowest cost, in the order they were found.
+    pub goals: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy + Ord> AllPathsResult<S, C> {
+    /// The cost of the cheapest path to any goal.
+    pub fn cost(&self) -> Option<C> {
+        self.distance(self.goals.first()?)
+    }
+
+    pub fn distance(&self, state: &S) -> Option<C> {
+        self.distances.get(state).copied()
+    }
+
+    /// The number of distinct cheapest paths from the start to any of the goals.
+    pub fn count_paths(&self) -> BigUint {
+        self.count_paths_to_all(&self.goals)
+    }
+
+    /// The number of distinct cheapest paths from the start to a reached state, or zero if the
+    /// state was not reached.
+    pub fn count_paths_to(&self, state: &S) -> BigUint {
+        self.count_paths_to_all(std::slice::from_ref(state))
+    }
+
+    fn count_paths_to_all(&self, targets: &[S]) -> BigUint {
+        // NOTE: predecessors are always cheaper than their successors, so processing the states
+        // by cost guarantees that the counts of all predecessors are known.
+        let mut states = Vec::from_iter(self.states_on_paths_to(targets));
+        states.sort_by_key(|state| self.distances[state]);
+
+        let mut counts = HashMap::<&S, BigUint>::with_capacity(states.len());
+        for state in &states {
+            let count = match self.predecessors.get(state) {
+                Some(predecessors) => predecessors.iter().map(|p| &counts[p]).sum(),
+                None => BigUint::one(),
+            };
+            counts.insert(state, count);
+        }
+
+        targets.iter().filter_map(|target| counts.get(target)).sum()
+    }
+
+    /// All states on any cheapest path to any of the goals, including the start and the goals.
+    pub fn states_on_paths(&self) -> HashSet<S> {
+        self.states_on_paths_to(&self.goals)
+    }
+
+    /// All states on any cheapest path to any of the given states, including the start and the
+    /// targets themselves. Targets that were not reached are ignored.
+    pub fn states_on_paths_to(&self, targets: &[S]) -> HashSet<S> {
+        let mut stack = targets
+            .iter()
+            .filter(|target| self.distances.contains_key(target))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut seen = HashSet::from_iter(stack.iter().cloned());
+
+        while let Some(state) = stack.pop() {
+            for previous in self.predecessors.get(&state).into_iter().flatten() {
+                if seen.insert(previous.clone()) {
+                    stack.push(previous.clone());
+                }
+            }
+        }
+        seen
+    }
+
+    /// Every distinct cheapest path from the start to any of the goals, including both.
+    ///
+    /// The number of paths can grow exponentially, prefer [`Self::count_paths`] if only their
+    /// number is needed.
+    pub fn paths(&self) -> Vec<Vec<S>> {
+        let mut paths = Vec::new();
+        // Partial paths, walked backwards from a goal.
+        let mut stack = self
+            .goals
+            .iter()
+            .map(|goal| vec![goal.clone()])
+            .collect::<Vec<_>>();
+
+        while let Some(mut path) = stack.pop() {
+            match self.predecessors.get(path.last().unwrap()) {
+                Some(predecessors) => stack.extend(predecessors.iter().map(|previous| {
+                    let mut path = path.clone();
+                    path.push(previous.clone());
+                    path
+                })),
+                None => {
+                    path.reverse();
+                    paths.push(path);
+                }
+            }
+        }
+        paths
+    }
+}
+
+/// Like [`dijkstra`], but records every optimal predecessor of each state and finds all goals that
+/// can be reached at the lowest cost. Successors must have positive costs.
+///
+/// Without a goal, the optimal paths to every reachable state are recorded.
+pub fn dijkstra_all<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> AllPathsResult<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut result = AllPathsResult {
+        start: start.clone(),
+        distances: HashMap::from([(start.clone(), C::zero())]),
+        predecessors: HashMap::new(),
+        goals: Vec::new(),
+    };
+
+    // NOTE: states are stored separately, so that they don't need to implement `Ord`.
+    let mut states = vec![start];
+    let mut heap = BinaryHeap::from([Reverse((C::zero(), 0))]);
+
+    while let Some(Reverse((cost, id))) = heap.pop() {
+        // Once a goal was found, only other goals with the same cost are of interest.
+        if let Some(goal_cost) = result.cost() {
+            if cost > goal_cost {
+                break;
+            }
+        }
+
+        let state = states[id].clone();
+        if result.distances[&state] < cost {
+            continue;
+        }
+        if is_goal(&state) {
+            result.goals.push(state);
+            continue;
+        }
+        if !result.goals.is_empty() {
+            continue;
+        }
+
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+            match result.distances.get(&next) {
+                Some(&known) if known < next_cost => continue,
+                Some(&known) if known == next_cost => {
+                    result
+                        .predecessors
+                        .get_mut(&next)
+                        .unwrap()
+                        .push(state.clone());
+                    continue;
+                }
+                _ => {}
+            }
+
+            result.distances.insert(next.clone(), next_cost);
+            result
+                .predecessors
+                .insert(next.clone(), vec![state.clone()]);
+            heap.push(Reverse((next_cost, states.len())));
+            states.push(next);
+        }
+    }
+
+    result
+}
+
+/// Like [`bfs`], but records every optimal predecessor of each state and finds all goals that can
+/// be reached in the fewest steps.
+pub fn bfs_all<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> AllPathsResult<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    dijkstra_all(
+        start,
+        |state| successors(state).into_iter().map(|next| (next, 1)),
+        is_goal,
+    )
+}
+
+/// The number of steps from the nearest of the given sources to every cell of a grid, moving
+/// orthogonally through cells that are passable. Cells that can't be reached are `None`.
+///
+/// Sources are always at distance 0, even if they are not passable themselves.
+pub fn distance_field<T>(
+    grid: &Grid<T>,
+    sources: impl IntoIterator<Item = Pos<usize>>,
+    mut is_passable: impl FnMut(&T) -> bool,
+) -> Array2<Option<usize>> {
+    let mut distances = Array2::from_elem(grid.dim().tuple(), None);
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if distances[source.tuple()].is_none() {
+            distances[source.tuple()] = Some(0);
+            queue.push_back(source);
+        }
+    }
+
+    while let Some(pos) = queue.pop_front() {
+        let next_dist = distances[pos.tuple()].unwrap() + 1;
+        for (next, cell) in grid.neighbor_cells(pos) {
+            if distances[next.tuple()].is_none() && is_passable(cell) {
+                distances[next.tuple()] = Some(next_dist);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Get the positions within a Manhattan distance of a given position (excluding itself), if they
+/// are within the given bounds, together with their distance.
+pub fn positions_within(
+    pos: Pos<usize>,
+    radius: usize,
+    bounds: impl Into<Pos<usize>>,
+) -> impl Iterator<Item = (Pos<usize>, usize)> {
+    let bounds = bounds.into();
+    let radius = radius as isize;
+    (-radius..=radius).flat_map(move |dr| {
+        let remaining = radius - dr.abs();
+        (-remaining..=remaining).filter_map(move |dc| {
+            let other = (pos + Pos(dr, dc)).in_bounds(bounds)?;
+            (other != pos).then_some((other, dr.unsigned_abs() + dc.unsigned_abs()))
+        })
+    })
+}
+
+/// All ordered pairs of reached cells of a distance field that are at most `radius` apart, with
+/// their Manhattan distance, e.g. to find shortcuts through walls. Every pair is yielded in both
+/// directions.
+pub fn pairs_within(
+    field: &Array2<Option<usize>>,
+    radius: usize,
+) -> impl Iterator<Item = (Pos<usize>, Pos<usize>, usize)> + '_ {
+    let bounds = Pos::from(field.dim());
+    field
+        .indexed_iter()
+        .filter(|(_, dist)| dist.is_some())
+        .flat_map(move |(from, _)| {
+            let from = Pos::from(from);
+            positions_within(from, radius, bounds)
+                .filter(|(to, _)| field[to.tuple()].is_some())
+                .map(move |(to, dist)| (from, to, dist))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+
+    use super::{
+        astar, bfs, bfs_all, dijkstra, dijkstra_all, distance_field, pairs_within, positions_within,
+    };
+    use crate::{Grid, Pos};
+
+    const MAZE: &str = "\
+..#....
+.##.##.
+....#..
+.##...#
+...#...";
+
+    fn open_neighbors(grid: &Grid<char>, pos: Pos<usize>) -> Vec<Pos<usize>> {
+        grid.neighbor_cells(pos)
+            .filter(|(_, &c)| c == '.')
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    #[test]
+    fn finds_shortest_path_with_bfs() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let end = Pos(4, 6);
+        let result = bfs(
+            Pos(0, 0),
+            |&pos| open_neighbors(&grid, pos),
+            |&pos| pos == end,
+        );
+
+        assert_eq!(result.cost(), Some(10));
+        let path = result.path().unwrap();
+        assert_eq!(path.len(), 11);
+        assert_eq!(path[0], Pos(0, 0));
+        assert_eq!(path[10], end);
+        assert!(path
+            .windows(2)
+            .all(|step| step[0].manhattan_distance(step[1]) == 1));
+    }
+
+    #[test]
+    fn explores_everything_without_goal() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let result = bfs(Pos(0, 0), |&pos| open_neighbors(&grid, pos), |_| false);
+
+        assert_eq!(result.goal, None);
+        assert_eq!(result.cost(), None);
+        assert_eq!(result.distances.len(), grid.positions_of(&'.').count());
+        assert_eq!(result.distance(&Pos(0, 3)), Some(7));
+        assert_eq!(result.path_to(&Pos(1, 0)), Some(vec![Pos(0, 0), Pos(1, 0)]));
+        assert_eq!(result.path_to(&Pos(0, 2)), None);
+    }
+
+    #[test]
+    fn finds_cheapest_path_with_weights() {
+        // Going through `b` takes fewer steps, but going through `c` is cheaper.
+        let successors = |&node: &char| match node {
+            'a' => vec![('b', 10), ('c', 1)],
+            'b' => vec![('e', 1)],
+            'c' => vec![('d', 1)],
+            'd' => vec![('e', 1)],
+            _ => vec![],
+        };
+
+        let result = dijkstra('a', successors, |&node| node == 'e');
+        assert_eq!(result.cost(), Some(3));
+        assert_eq!(result.path(), Some(vec!['a', 'c', 'd', 'e']));
+
+        let result = astar('a', successors, |&node| node == 'e', |_| 0);
+        assert_eq!(result.cost(), Some(3));
+        assert_eq!(dijkstra('a', successors, |&node| node == 'x').cost(), None);
+    }
+
+    #[test]
+    fn uses_heuristic() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let end = Pos(4, 6);
+        let mut expanded = 0;
+        let result = astar(
+            Pos(0, 0),
+            |&pos| {
+                expanded += 1;
+                open_neighbors(&grid, pos).into_iter().map(|next| (next, 1))
+            },
+            |&pos| pos == end,
+            |pos| pos.manhattan_distance(end),
+        );
+
+        assert_eq!(result.cost(), Some(10));
+        assert!(expanded < grid.positions_of(&'.').count());
+    }
+
+    #[test]
+    fn counts_all_shortest_paths() {
+        let grid = Grid::new(3, 3, '.');
+        let end = Pos(2, 2);
+        let result = bfs_all(
+            Pos(0, 0),
+            |&pos| open_neighbors(&grid, pos),
+            |&pos| pos == end,
+        );
+
+        assert_eq!(result.cost(), Some(4));
+        assert_eq!(result.goals, vec![end]);
+        assert_eq!(result.count_paths(), BigUint::from(6u8));
+        assert_eq!(result.count_paths_to(&Pos(1, 1)), BigUint::from(2u8));
+        assert_eq!(result.states_on_paths().len(), 9);
+
+        let paths = result.paths();
+        assert_eq!(paths.len(), 6);
+        assert!(paths.iter().all(|path| path.len() == 5 && path[4] == end));
+    }
+
+    #[test]
+    fn collects_states_on_cheapest_paths() {
+        // Both `b` and `c` lead to `d` at the same cost, `e` is a more expensive detour.
+        let successors = |&node: &char| match node {
+            'a' => vec![('b', 1), ('c', 2), ('e', 1)],
+            'b' => vec![('d', 2)],
+            'c' => vec![('d', 1)],
+            'e' => vec![('d', 5)],
+            'd' => vec![('f', 1), ('g', 1)],
+            _ => vec![],
+        };
+
+        let result = dijkstra_all('a', successors, |&node| node == 'd');
+        assert_eq!(result.cost(), Some(3));
+        assert_eq!(result.predecessors[&'d'].len(), 2);
+        assert_eq!(result.count_paths(), BigUint::from(2u8));
+        let mut states = Vec::from_iter(result.states_on_paths());
+        states.sort();
+        assert_eq!(states, vec!['a', 'b', 'c', 'd']);
+
+        // All goals with the lowest cost are found, but none that are more expensive.
+        let result = dijkstra_all('a', successors, |&node| "cdf".contains(node));
+        assert_eq!(result.goals, vec!['c']);
+        let result = dijkstra_all('a', successors, |&node| "fg".contains(node));
+        assert_eq!(result.goals.len(), 2);
+        assert_eq!(result.count_paths(), BigUint::from(4u8));
+        assert_eq!(
+            dijkstra_all('a', successors, |_| false).count_paths(),
+            BigUint::ZERO
+        );
+    }
+
+    #[test]
+    fn computes_distance_field() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let field = distance_field(&grid, [Pos(0, 0)], |&c| c == '.');
+        assert_eq!(field[(0, 0)], Some(0));
+        assert_eq!(field[(4, 6)], Some(10));
+        assert_eq!(field[(0, 2)], None);
+
+        // Every cell is as far as its nearest source.
+        let field = distance_field(&grid, [Pos(0, 0), Pos(4, 6)], |&c| c == '.');
+        assert_eq!(field[(4, 6)], Some(0));
+        assert_eq!(field[(4, 5)], Some(1));
+        assert_eq!(field[(3, 5)], Some(2));
+        assert_eq!(field[(2, 0)], Some(2));
+    }
+
+    #[test]
+    fn finds_pairs_within_radius() {
+        let mut within = positions_within(Pos(0, 1), 2, Pos(3, 3)).collect::<Vec<_>>();
+        within.sort();
+        assert_eq!(
+            within,
+            vec![
+                (Pos(0, 0), 1),
+                (Pos(0, 2), 1),
+                (Pos(1, 0), 2),
+                (Pos(1, 1), 1),
+                (Pos(1, 2), 2),
+                (Pos(2, 1), 2)
+            ]
+        );
+
+        let grid: Grid<char> = "..\n#.".parse().unwrap();
+        let field = distance_field(&grid, [Pos(0, 0)], |&c| c == '.');
+        let pairs = pairs_within(&field, 1).collect::<Vec<_>>();
+        assert_eq!(pairs.len(), 4);
+        assert!(pairs.contains(&(Pos(1, 1), Pos(0, 1), 1)));
+        assert_eq!(pairs_within(&field, 2).count(), 6);
+    }
+}
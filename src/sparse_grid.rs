@@ -0,0 +1,192 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use crate::{FromChar, ParseGridError, Pos, ToChar};
+
+/// An unbounded grid that only stores occupied cells, e.g. for puzzles where things move off the
+/// initial map or the map grows in every direction.
+///
+/// The bounding box of all occupied cells is tracked automatically and used to render the grid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos<isize>, T>,
+    /// The smallest and largest row and column of any cell, inclusive.
+    bounds: Option<(Pos<isize>, Pos<isize>)>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The top left and bottom right corner of the bounding box of all cells, inclusive.
+    pub fn bounds(&self) -> Option<(Pos<isize>, Pos<isize>)> {
+        self.bounds
+    }
+
+    pub fn get(&self, pos: Pos<isize>) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos<isize>) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn contains(&self, pos: Pos<isize>) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    /// Set a cell, returning its previous value.
+    pub fn insert(&mut self, pos: Pos<isize>, value: T) -> Option<T> {
+        self.bounds = Some(expand(self.bounds, pos));
+        self.cells.insert(pos, value)
+    }
+
+    /// Clear a cell, returning its value.
+    pub fn remove(&mut self, pos: Pos<isize>) -> Option<T> {
+        let value = self.cells.remove(&pos)?;
+
+        // NOTE: only cells on the edge of the bounding box can shrink it.
+        if let Some((min, max)) = self.bounds {
+            if pos.0 == min.0 || pos.1 == min.1 || pos.0 == max.0 || pos.1 == max.1 {
+                self.bounds = self.compute_bounds();
+            }
+        }
+
+        Some(value)
+    }
+
+    /// Iterate over all cells in arbitrary order, together with their positions.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos<isize>, &T)> {
+        self.cells.iter().map(|(&pos, cell)| (pos, cell))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos<isize>> + '_ {
+        self.cells.keys().copied()
+    }
+
+    fn compute_bounds(&self) -> Option<(Pos<isize>, Pos<isize>)> {
+        self.cells
+            .keys()
+            .fold(None, |bounds, &pos| Some(expand(bounds, pos)))
+    }
+
+    /// Render the bounding box of all cells, using the given character for empty cells.
+    pub fn render(&self, empty: char) -> String
+    where
+        T: ToChar,
+    {
+        let Some((min, max)) = self.bounds else {
+            return String::new();
+        };
+
+        (min.0..=max.0)
+            .map(|row| {
+                (min.1..=max.1)
+                    .map(|col| self.get(Pos(row, col)).map_or(empty, ToChar::to_char))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Grow a bounding box to include the given position.
+fn expand(bounds: Option<(Pos<isize>, Pos<isize>)>, pos: Pos<isize>) -> (Pos<isize>, Pos<isize>) {
+    match bounds {
+        None => (pos, pos),
+        Some((min, max)) => (
+            Pos(min.0.min(pos.0), min.1.min(pos.1)),
+            Pos(max.0.max(pos.0), max.1.max(pos.1)),
+        ),
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<(Pos<isize>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos<isize>, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for (pos, value) in iter {
+            grid.insert(pos, value);
+        }
+        grid
+    }
+}
+
+/// Renders the bounding box of all cells with `.` for empty cells.
+impl<T: ToChar> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render('.'))
+    }
+}
+
+/// Parses a map with `.` for empty cells. The top left corner of the map is at `Pos(0, 0)`.
+impl<T: FromChar> FromStr for SparseGrid<T> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut grid = Self::new();
+
+        for (row, line) in s.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                if c == '.' {
+                    continue;
+                }
+                let cell = T::from_char(c).ok_or(ParseGridError::InvalidCell { c, row, col })?;
+                grid.insert(Pos(row as isize, col as isize), cell);
+            }
+        }
+
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SparseGrid;
+    use crate::Pos;
+
+    #[test]
+    fn tracks_bounds() {
+        let mut grid: SparseGrid<char> = "#..\n..#".parse().unwrap();
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.bounds(), Some((Pos(0, 0), Pos(1, 2))));
+
+        grid.insert(Pos(-2, 4), 'x');
+        assert_eq!(grid.bounds(), Some((Pos(-2, 0), Pos(1, 4))));
+
+        assert_eq!(grid.remove(Pos(-2, 4)), Some('x'));
+        assert_eq!(grid.remove(Pos(5, 5)), None);
+        assert_eq!(grid.bounds(), Some((Pos(0, 0), Pos(1, 2))));
+
+        grid.remove(Pos(0, 0));
+        grid.remove(Pos(1, 2));
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn renders_bounding_box() {
+        let grid: SparseGrid<char> = [(Pos(-1, -1), '#'), (Pos(1, 2), 'O')].into_iter().collect();
+        assert_eq!(grid.get(Pos(1, 2)), Some(&'O'));
+        assert!(!grid.contains(Pos(0, 0)));
+        assert_eq!(grid.to_string(), "#...\n....\n...O");
+        assert_eq!(grid.render(' '), "#   \n    \n   O");
+        assert_eq!(SparseGrid::<char>::new().to_string(), "");
+    }
+}
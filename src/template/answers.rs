@@ -0,0 +1,353 @@
+/// Answers of solution parts and their submission history.
+/// The runner records the answer of every part it runs and the verdict of every submission.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{config::config, Day};
+
+/// The verdict of Advent of Code on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+}
+
+impl Verdict {
+    /// Parse the verdict from the output of `aoc submit`. Returns `None` if the answer was not
+    /// judged, e.g. because of rate limiting or because the part is already solved.
+    pub fn from_aoc_output(output: &str) -> Option<Self> {
+        if output.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if output.contains("That's not the right answer") {
+            Some(Verdict::Incorrect)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// The known answers of a single part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part: u8,
+    /// The answer of the last run, `None` if the part was not solved.
+    pub last_answer: Option<String>,
+    pub submissions: Vec<Submission>,
+}
+
+impl Answer {
+    /// The answer that was accepted on submission, if any.
+    pub fn correct_answer(&self) -> Option<&str> {
+        self.submissions
+            .iter()
+            .find(|s| s.verdict == Verdict::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    pub fn incorrect_submissions(&self) -> usize {
+        self.submissions
+            .iter()
+            .filter(|s| s.verdict == Verdict::Incorrect)
+            .count()
+    }
+}
+
+/// Known answers of all parts. Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(&config().paths.answers)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    /// Fails if the file can't be read or parsed, so that callers don't overwrite it.
+    pub fn read_from_file() -> Result<Self, String> {
+        let path = &config().paths.answers;
+        let s = match fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(e) => return Err(format!("could not read {path}: {e}")),
+        };
+
+        Answers::try_from(s).map_err(|e| format!("could not parse {path}: {e}"))
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&Answer> {
+        self.data.iter().find(|a| a.day == day && a.part == part)
+    }
+
+    fn entry(&mut self, day: Day, part: u8) -> &mut Answer {
+        let index = match self
+            .data
+            .iter()
+            .position(|a| a.day == day && a.part == part)
+        {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part,
+                    last_answer: None,
+                    submissions: vec![],
+                });
+                self.data.sort_unstable_by_key(|a| (a.day, a.part));
+                self.data
+                    .iter()
+                    .position(|a| a.day == day && a.part == part)
+                    .unwrap()
+            }
+        };
+        &mut self.data[index]
+    }
+
+    /// Record the answer of a run. Returns whether it differs from the last recorded answer.
+    pub fn record_run(&mut self, day: Day, part: u8, answer: Option<String>) -> bool {
+        let entry = self.entry(day, part);
+        if entry.last_answer == answer {
+            return false;
+        }
+        entry.last_answer = answer;
+        true
+    }
+
+    pub fn record_submission(&mut self, day: Day, part: u8, answer: String, verdict: Verdict) {
+        self.entry(day, part)
+            .submissions
+            .push(Submission { answer, verdict });
+    }
+}
+
+// --------------------------------------------------------------------------
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+// --------------------------------------------------------------------------
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert(
+            "last_answer".into(),
+            value
+                .last_answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "submissions".into(),
+            JsonValue::Array(value.submissions.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected answer.part to be a number.")?;
+
+        let last_answer = json
+            .get("last_answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.last_answer to be null or string.")?;
+
+        let submissions = json
+            .get("submissions")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected answer.submissions to be an array.")?
+            .iter()
+            .map(Submission::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Answer {
+            day,
+            part: *part as u8,
+            last_answer: last_answer.cloned(),
+            submissions,
+        })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(
+                match value.verdict {
+                    Verdict::Correct => "correct",
+                    Verdict::Incorrect => "incorrect",
+                }
+                .into(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = match json.get("verdict").and_then(|v| v.get::<String>()) {
+            Some(v) if v == "correct" => Verdict::Correct,
+            Some(v) if v == "incorrect" => Verdict::Incorrect,
+            _ => return Err("Expected submission.verdict to be `correct` or `incorrect`.".into()),
+        };
+
+        Ok(Submission {
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+// --------------------------------------------------------------------------
+
+#[cfg(all(feature = "test_lib", test))]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Answers, Verdict};
+    use crate::day;
+
+    fn get_mock_answers() -> Answers {
+        let mut answers = Answers::default();
+        answers.record_run(day!(2), 1, Some("42".into()));
+        answers.record_run(day!(1), 2, None);
+        answers.record_submission(day!(2), 1, "41".into(), Verdict::Incorrect);
+        answers.record_submission(day!(2), 1, "42".into(), Verdict::Correct);
+        answers
+    }
+
+    #[test]
+    fn records_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+
+        let answer = answers.get(day!(2), 1).unwrap();
+        assert_eq!(answer.last_answer.as_deref(), Some("42"));
+        assert_eq!(answer.correct_answer(), Some("42"));
+        assert_eq!(answer.incorrect_submissions(), 1);
+
+        assert!(answers.get(day!(1), 2).unwrap().last_answer.is_none());
+        assert!(answers.get(day!(3), 1).is_none());
+    }
+
+    #[test]
+    fn records_only_changed_answers() {
+        let mut answers = get_mock_answers();
+        assert!(!answers.record_run(day!(2), 1, Some("42".into())));
+        assert!(!answers.record_run(day!(1), 2, None));
+        assert!(answers.record_run(day!(2), 1, Some("43".into())));
+        assert!(answers.record_run(day!(3), 1, Some("1".into())));
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let answers = get_mock_answers();
+        let json = JsonValue::from(answers.clone()).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap().data, answers.data);
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_aoc_output("That's the right answer! You are one gold star closer."),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::from_aoc_output("That's not the right answer; your answer is too low."),
+            Some(Verdict::Incorrect)
+        );
+        assert_eq!(
+            Verdict::from_aoc_output("You gave an answer too recently."),
+            None
+        );
+    }
+}
@@ -4,7 +4,7 @@ use std::{
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{config::config, Day};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -77,22 +77,27 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    // NOTE: stdout is captured to parse the verdict and echoed afterwards.
+    let output = call_aoc_cli_with(&args, Stdio::piped());
+    let stdout = match &output {
+        Ok(output) | Err(AocCommandError::BadExitStatus(output)) => &output.stdout,
+        Err(_) => return output,
+    };
+    print!("{}", String::from_utf8_lossy(stdout));
+    output
 }
 
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    config().paths.input(day)
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    config().paths.puzzle(day)
 }
 
 fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+    config().year()
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
@@ -109,10 +114,14 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
 }
 
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+    call_aoc_cli_with(args, Stdio::inherit())
+}
+
+fn call_aoc_cli_with(args: &[String], stdout: Stdio) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
     let output = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
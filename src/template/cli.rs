@@ -0,0 +1,320 @@
+/// Declarative description of the command-line interface, used to render help texts and to
+/// generate shell completions. Parsing itself happens in `main.rs`.
+use std::{fmt::Display, str::FromStr};
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A positional argument or an option of a command.
+pub struct Arg {
+    /// The name of an option (e.g. `--release`) or the placeholder of a positional argument (e.g.
+    /// `<day>`).
+    pub name: &'static str,
+    /// The placeholder of the value an option takes, if any.
+    pub value: Option<&'static str>,
+    /// The possible values, offered by shell completions.
+    pub values: &'static [&'static str],
+    pub help: &'static str,
+}
+
+impl Arg {
+    /// The names of an option, e.g. `-h` and `--help` for `-h, --help`.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> {
+        self.name.split(", ")
+    }
+
+    /// Whether a positional argument may be omitted, i.e. is written as `[name]`.
+    pub fn is_optional(&self) -> bool {
+        self.name.starts_with('[')
+    }
+
+    const fn flag(name: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            value: None,
+            values: &[],
+            help,
+        }
+    }
+
+    const fn option(
+        name: &'static str,
+        value: &'static str,
+        values: &'static [&'static str],
+        help: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            value: Some(value),
+            values,
+            help,
+        }
+    }
+
+    const fn positional(name: &'static str, help: &'static str) -> Self {
+        Self::flag(name, help)
+    }
+
+    fn usage(&self) -> String {
+        match self.value {
+            Some(value) => format!("{} {value}", self.name),
+            None => self.name.to_string(),
+        }
+    }
+}
+
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub positionals: &'static [Arg],
+    pub options: &'static [Arg],
+}
+
+const DAY: Arg = Arg::positional("<day>", "Day of the puzzle, 1 to 25");
+const PART: Arg = Arg::option("--part", "<part>", &["1", "2"], "Only run the given part");
+const FORMATS: &[&str] = &["pretty", "plain", "json", "junit"];
+const FORMAT: Arg = Arg::option(
+    "--format",
+    "<format>",
+    FORMATS,
+    "Output format, one of pretty, plain, json or junit",
+);
+const MEMORY: Arg = Arg::flag(
+    "--memory",
+    "Report allocations and peak heap usage per part",
+);
+const RELEASE: Arg = Arg::flag("--release", "Run an optimized build");
+const VARIANTS: Arg = Arg::flag(
+    "--variants",
+    "Also run the variants of each part and check that they agree",
+);
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "scaffold",
+        about: "Create the solution, input and example files for a day",
+        positionals: &[DAY],
+        options: &[
+            Arg::flag("--download", "Download the input and puzzle afterwards"),
+            Arg::flag("--overwrite", "Overwrite an existing solution file"),
+        ],
+    },
+    Command {
+        name: "download",
+        about: "Download the input and puzzle description of one or more days",
+        positionals: &[Arg::positional(
+            "[days]",
+            "Days to download, e.g. 1-5,9,12. Several days skip inputs that are already present",
+        )],
+        options: &[Arg::flag(
+            "--all",
+            "Download every unlocked day of the year whose input is missing",
+        )],
+    },
+    Command {
+        name: "read",
+        about: "Print the puzzle description of a day",
+        positionals: &[DAY],
+        options: &[Arg::option(
+            "--part",
+            "<part>",
+            &["1", "2"],
+            "Start at the given part of the description",
+        )],
+    },
+    Command {
+        name: "solve",
+        about: "Run the solution of a day against its input",
+        positionals: &[Arg::positional(
+            "<days>",
+            "Day to run, or a list of days and ranges like 1-5,9,12",
+        )],
+        options: &[
+            RELEASE,
+            PART,
+            Arg::option(
+                "--submit",
+                "<part>",
+                &["1", "2"],
+                "Submit the answer of a part",
+            ),
+            Arg::flag("--dhat", "Profile heap usage with dhat"),
+            MEMORY,
+            VARIANTS,
+            Arg::flag(
+                "--check-determinism",
+                "Rerun each part several times and report differing answers",
+            ),
+            FORMAT,
+        ],
+    },
+    Command {
+        name: "all",
+        about: "Run the solutions of all days",
+        positionals: &[],
+        options: &[RELEASE, PART, FORMAT],
+    },
+    Command {
+        name: "time",
+        about: "Benchmark solutions",
+        positionals: &[Arg::positional(
+            "[days]",
+            "Days to benchmark, e.g. 1-5,9,12. By default, days without stored timings are benched",
+        )],
+        options: &[
+            Arg::flag("--all", "Benchmark all days"),
+            PART,
+            Arg::flag("--store", "Store the timings in the readme"),
+            MEMORY,
+            VARIANTS,
+            FORMAT,
+        ],
+    },
+    Command {
+        name: "status",
+        about: "Show the state of every day: files, answers, submissions and timings",
+        positionals: &[],
+        options: &[],
+    },
+    Command {
+        name: "inputs",
+        about: "Encrypt inputs so they can be committed, or restore them from the encrypted files",
+        positionals: &[
+            Arg {
+                name: "<action>",
+                value: None,
+                values: &["encrypt", "decrypt", "keygen"],
+                help: "One of encrypt, decrypt or keygen (create a key file)",
+            },
+            Arg::positional(
+                "[days]",
+                "Days to process, e.g. 1-5,9,12. By default, all days",
+            ),
+        ],
+        options: &[],
+    },
+    Command {
+        name: "completions",
+        about: "Print a shell completion script for the installed binary, not the cargo aliases",
+        positionals: &[Arg {
+            name: "<shell>",
+            value: None,
+            values: SHELLS,
+            help: "Shell to generate completions for, one of bash, zsh or fish",
+        }],
+        options: &[],
+    },
+    #[cfg(feature = "today")]
+    Command {
+        name: "today",
+        about: "Scaffold, download and read the puzzle of the current day",
+        positionals: &[],
+        options: &[],
+    },
+];
+
+pub const HELP: Arg = Arg::flag("-h, --help", "Print help");
+pub const VERSION_ARG: Arg = Arg::flag("-V, --version", "Print version");
+
+fn print_args(title: &str, args: &[&Arg]) {
+    if args.is_empty() {
+        return;
+    }
+
+    let width = args.iter().map(|arg| arg.usage().len()).max().unwrap_or(0);
+
+    println!();
+    println!("{ANSI_BOLD}{title}:{ANSI_RESET}");
+    for arg in args {
+        println!("  {:<width$}  {}", arg.usage(), arg.help);
+    }
+}
+
+pub fn print_help() {
+    println!("Solutions for Advent of Code in Rust.");
+    println!();
+    println!("{ANSI_BOLD}Usage:{ANSI_RESET} cargo <command> [options]");
+    println!();
+    println!("{ANSI_BOLD}Commands:{ANSI_RESET}");
+
+    let width = COMMANDS.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    for command in COMMANDS {
+        println!("  {:<width$}  {}", command.name, command.about);
+    }
+
+    print_args("Options", &[&HELP, &VERSION_ARG]);
+
+    println!();
+    println!("Run `cargo <command> --help` for the options of a command.");
+}
+
+pub fn print_command_help(command: &Command) {
+    let mut usage = format!("cargo {}", command.name);
+    for arg in command.positionals {
+        usage.push(' ');
+        usage.push_str(arg.name);
+    }
+    if !command.options.is_empty() {
+        usage.push_str(" [options]");
+    }
+
+    println!("{}.", command.about);
+    println!();
+    println!("{ANSI_BOLD}Usage:{ANSI_RESET} {usage}");
+
+    print_args("Arguments", &command.positionals.iter().collect::<Vec<_>>());
+
+    let mut options: Vec<&Arg> = command.options.iter().collect();
+    options.push(&HELP);
+    print_args("Options", &options);
+}
+
+pub fn find_command(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|command| command.name == name)
+}
+
+pub fn print_version() {
+    println!("advent_of_code {VERSION}");
+}
+
+// --------------------------------------------------------------------------
+
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+/// Shells that completion scripts can be generated for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = ShellFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(ShellFromStrError(s.to_string())),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Shell`].
+#[derive(Debug)]
+pub struct ShellFromStrError(String);
+
+impl std::error::Error for ShellFromStrError {}
+
+impl Display for ShellFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unsupported shell `{}`, expecting one of `bash`, `zsh` or `fish`",
+            self.0
+        )
+    }
+}
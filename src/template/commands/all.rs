@@ -1,5 +1,5 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{all_days, run_multi::run_multi, RunOptions};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(options: &RunOptions) {
+    run_multi(&all_days().collect(), options).exit_on_failure();
 }
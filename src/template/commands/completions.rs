@@ -0,0 +1,245 @@
+use crate::template::cli::{Arg, Command, Shell, COMMANDS, HELP, VERSION_ARG};
+
+/// Name of the binary the completions are registered for.
+const BIN: &str = "advent_of_code";
+
+pub fn handle(shell: Shell) {
+    let script = match shell {
+        Shell::Bash => bash(),
+        Shell::Zsh => zsh(),
+        Shell::Fish => fish(),
+    };
+    print!("{script}");
+}
+
+/// Options of a command, including `--help`.
+fn options(command: &Command) -> impl Iterator<Item = &Arg> {
+    command.options.iter().chain([&HELP])
+}
+
+fn bash() -> String {
+    let mut top_level: Vec<&str> = COMMANDS.iter().map(|c| c.name).collect();
+    top_level.extend(HELP.names().chain(VERSION_ARG.names()));
+
+    let mut cases = String::new();
+    for command in COMMANDS {
+        let mut words: Vec<&str> = options(command).flat_map(Arg::names).collect();
+        words.extend(command.positionals.iter().flat_map(|arg| arg.values));
+
+        cases.push_str(&format!("        {})\n", command.name));
+        cases.push_str("            case \"$prev\" in\n");
+        for option in options(command).filter(|arg| arg.value.is_some()) {
+            cases.push_str(&format!(
+                "                {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")); return ;;\n",
+                option.name,
+                option.values.join(" ")
+            ));
+        }
+        cases.push_str("            esac\n");
+        cases.push_str(&format!(
+            "            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n",
+            words.join(" ")
+        ));
+        cases.push_str("            ;;\n");
+    }
+
+    format!(
+        r#"_{BIN}() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+
+    if [ "$COMP_CWORD" -eq 1 ]; then
+        COMPREPLY=($(compgen -W "{}" -- "$cur"))
+        return
+    fi
+
+    case "${{COMP_WORDS[1]}}" in
+{cases}    esac
+}}
+
+complete -F _{BIN} {BIN}
+"#,
+        top_level.join(" ")
+    )
+}
+
+fn zsh() -> String {
+    let commands: String = COMMANDS
+        .iter()
+        .map(|c| format!("        '{}:{}'\n", c.name, zsh_quote(c.about)))
+        .collect();
+
+    let mut cases = String::new();
+    for command in COMMANDS {
+        cases.push_str(&format!("        {})\n", command.name));
+        cases.push_str("            _arguments");
+        for option in options(command) {
+            for name in option.names() {
+                let action = match option.value {
+                    Some(value) => format!(
+                        ":{}:({})",
+                        value.trim_matches(['<', '>']),
+                        option.values.join(" ")
+                    ),
+                    None => String::new(),
+                };
+                cases.push_str(&format!(
+                    " \\\n                '{name}[{}]{action}'",
+                    zsh_quote(&zsh_escape_spec(option.help))
+                ));
+            }
+        }
+        for arg in command.positionals {
+            let colon = if arg.is_optional() { "::" } else { ":" };
+            let name = arg.name.trim_matches(['<', '>', '[', ']']);
+            let action = if arg.values.is_empty() {
+                " ".to_string()
+            } else {
+                format!("({})", arg.values.join(" "))
+            };
+            cases.push_str(&format!(" \\\n                '{colon}{name}:{action}'"));
+        }
+        cases.push_str("\n            ;;\n");
+    }
+
+    format!(
+        r#"#compdef {BIN}
+
+_{BIN}() {{
+    local -a commands
+    commands=(
+{commands}    )
+
+    if (( CURRENT == 2 )); then
+        _describe 'command' commands
+        return
+    fi
+
+    shift words
+    (( CURRENT-- ))
+
+    case $words[1] in
+{cases}    esac
+}}
+
+if [ "$funcstack[1]" = "_{BIN}" ]; then
+    _{BIN} "$@"
+else
+    compdef _{BIN} {BIN}
+fi
+"#
+    )
+}
+
+fn fish() -> String {
+    let mut lines = vec![format!("complete -c {BIN} -f")];
+
+    for command in COMMANDS {
+        lines.push(format!(
+            "complete -c {BIN} -n __fish_use_subcommand -a {} -d '{}'",
+            command.name,
+            fish_quote(command.about)
+        ));
+    }
+    for option in [&HELP, &VERSION_ARG] {
+        lines.push(format!(
+            "complete -c {BIN} -n __fish_use_subcommand {} -d '{}'",
+            fish_names(option),
+            fish_quote(option.help)
+        ));
+    }
+
+    for command in COMMANDS {
+        let condition = format!("-n '__fish_seen_subcommand_from {}'", command.name);
+        for option in options(command) {
+            let values = match option.value {
+                Some(_) => format!(" -x -a '{}'", option.values.join(" ")),
+                None => String::new(),
+            };
+            lines.push(format!(
+                "complete -c {BIN} {condition} {}{values} -d '{}'",
+                fish_names(option),
+                fish_quote(option.help)
+            ));
+        }
+        for arg in command
+            .positionals
+            .iter()
+            .filter(|arg| !arg.values.is_empty())
+        {
+            lines.push(format!(
+                "complete -c {BIN} {condition} -a '{}' -d '{}'",
+                arg.values.join(" "),
+                fish_quote(arg.help)
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Escapes single quotes for use inside a single-quoted zsh word.
+fn zsh_quote(s: &str) -> String {
+    s.replace('\'', r"'\''")
+}
+
+/// Escapes the characters that are special in the `[description]` of a zsh `_arguments` spec.
+fn zsh_escape_spec(s: &str) -> String {
+    s.chars()
+        .flat_map(|c| match c {
+            '\\' | '[' | ']' | ':' => vec!['\\', c],
+            c => vec![c],
+        })
+        .collect()
+}
+
+/// Escapes backslashes and single quotes for use inside a single-quoted fish word.
+fn fish_quote(s: &str) -> String {
+    s.replace('\\', r"\\").replace('\'', r"\'")
+}
+
+/// Converts option names to the `-s` and `-l` arguments of fish' `complete`.
+fn fish_names(option: &Arg) -> String {
+    option
+        .names()
+        .map(|name| match name.strip_prefix("--") {
+            Some(long) => format!("-l {long}"),
+            None => format!("-s {}", name.trim_start_matches('-')),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(all(feature = "test_lib", test))]
+mod tests {
+    use super::{bash, fish, fish_quote, zsh, zsh_escape_spec, zsh_quote};
+    use crate::template::cli::COMMANDS;
+
+    #[test]
+    fn completes_all_commands() {
+        for script in [bash(), zsh(), fish()] {
+            for command in COMMANDS {
+                assert!(script.contains(command.name), "missing `{}`", command.name);
+            }
+        }
+    }
+
+    #[test]
+    fn completes_option_values() {
+        assert!(bash().contains(
+            r#"--format) COMPREPLY=($(compgen -W "pretty plain json junit" -- "$cur")); return ;;"#
+        ));
+        assert!(zsh().contains("'--submit[Submit the answer of a part]:part:(1 2)'"));
+        assert!(fish().contains(
+            "-n '__fish_seen_subcommand_from solve' -l format -x -a 'pretty plain json junit'"
+        ));
+    }
+
+    #[test]
+    fn escapes_help_texts() {
+        assert_eq!(zsh_quote("it's"), r"it'\''s");
+        assert_eq!(zsh_escape_spec("a [b]: c"), r"a \[b\]\: c");
+        assert_eq!(fish_quote(r"it's a \"), r"it\'s a \\");
+    }
+}
@@ -1,14 +1,157 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{all_days, aoc_cli, config::config, Day, DaySet};
+use std::{
+    fs,
+    path::Path,
+    process, thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Pause between two downloads, so that bulk downloads don't hammer the Advent of Code servers.
+const REQUEST_DELAY: Duration = Duration::from_secs(5);
+
+/// Puzzles unlock at midnight on the server, which runs on UTC-5.
+const UNLOCK_HOUR_UTC: u64 = 5;
 
 pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+    check_aoc_cli();
 
     if let Err(e) = aoc_cli::download(day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
 }
+
+/// Downloads several days at once, or all days if `days` is `None`. Days whose input is already
+/// present and days which are not unlocked yet are skipped.
+pub fn handle_many(days: Option<DaySet>) {
+    check_aoc_cli();
+
+    let days: Vec<Day> = match days {
+        Some(days) => {
+            let mut days: Vec<Day> = days.into_inner().into_iter().collect();
+            days.sort_unstable();
+            days
+        }
+        None => all_days().collect(),
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let year = config().year().unwrap_or_else(|| event_year(now));
+
+    let mut downloaded = 0;
+    let mut present = 0;
+    let mut locked = 0;
+    let mut failed = vec![];
+
+    for day in days {
+        if is_present(day) {
+            present += 1;
+            continue;
+        }
+        if now < unlock_time(year, day) {
+            locked += 1;
+            continue;
+        }
+
+        if downloaded + failed.len() > 0 {
+            thread::sleep(REQUEST_DELAY);
+        }
+
+        println!("Downloading day {day}...");
+        match aoc_cli::download(day) {
+            Ok(_) => downloaded += 1,
+            Err(e) => {
+                eprintln!("failed to download day {day}: {e}");
+                failed.push(day.to_string());
+            }
+        }
+    }
+
+    println!("---");
+    println!(
+        "Downloaded {downloaded} day(s), skipped {present} already present and {locked} not yet \
+        unlocked."
+    );
+
+    if !failed.is_empty() {
+        eprintln!("Failed to download day(s) {}.", failed.join(", "));
+        process::exit(1);
+    }
+}
+
+fn check_aoc_cli() {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+}
+
+/// Whether the input of a day exists, either as plaintext or encrypted (see `cargo inputs`).
+/// `cargo scaffold` creates empty inputs, those count as missing.
+fn is_present(day: Day) -> bool {
+    let paths = &config().paths;
+    fs::metadata(paths.input(day)).is_ok_and(|metadata| metadata.len() > 0)
+        || Path::new(&paths.encrypted_input(day)).exists()
+}
+
+/// The unix time at which the puzzle of a day unlocks.
+fn unlock_time(year: u16, day: Day) -> u64 {
+    let days = days_since_epoch(year.into(), 12, day.into_inner().into());
+    days * 24 * 3600 + UNLOCK_HOUR_UTC * 3600
+}
+
+/// The year of the latest event that has started at the given unix time. Like aoc-cli, this is the
+/// previous year until the 1st of december.
+fn event_year(now: u64) -> u16 {
+    // NOTE: the average length of a gregorian year is close enough to land on the right year or
+    // the next one around new year, which the check below takes care of.
+    let year = u16::try_from(1970 + now / 31_556_952).unwrap_or(u16::MAX);
+    if now < unlock_time(year, Day::__new_unchecked(1)) {
+        year - 1
+    } else {
+        year
+    }
+}
+
+/// Days from 1970-01-01 to the given date of the gregorian calendar.
+fn days_since_epoch(year: u64, month: u64, day: u64) -> u64 {
+    // See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(all(feature = "test_lib", test))]
+mod tests {
+    use super::{days_since_epoch, event_year, unlock_time};
+    use crate::day;
+
+    #[test]
+    fn counts_days_since_epoch() {
+        assert_eq!(days_since_epoch(1970, 1, 1), 0);
+        assert_eq!(days_since_epoch(2000, 3, 1), 11_017);
+        assert_eq!(days_since_epoch(2024, 12, 1), 20_058);
+    }
+
+    #[test]
+    fn unlocks_at_midnight_est() {
+        // 2024-12-01T05:00:00Z
+        assert_eq!(unlock_time(2024, day!(1)), 1_733_029_200);
+        assert_eq!(unlock_time(2024, day!(25)), 1_733_029_200 + 24 * 24 * 3600);
+    }
+
+    #[test]
+    fn finds_event_year() {
+        let unlock = unlock_time(2024, day!(1));
+        assert_eq!(event_year(unlock - 1), 2023);
+        assert_eq!(event_year(unlock), 2024);
+        // 2025-01-01T00:00:00Z and 2025-06-01T00:00:00Z
+        assert_eq!(event_year(1_735_689_600), 2024);
+        assert_eq!(event_year(1_748_736_000), 2024);
+    }
+}
@@ -0,0 +1,167 @@
+use std::{fmt::Display, fs, path::Path, process, str::FromStr};
+
+use crate::template::{
+    all_days,
+    config::config,
+    encryption::{InputKey, KEY_ENV_VAR},
+    Day, DaySet,
+};
+
+/// What `cargo inputs` should do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Encrypt plaintext inputs, so they can be committed.
+    Encrypt,
+    /// Restore missing plaintext inputs from their encrypted counterparts.
+    Decrypt,
+    /// Create a new key file.
+    Keygen,
+}
+
+pub fn handle(action: Action, days: Option<DaySet>) {
+    let days: Vec<Day> = match days {
+        Some(days) => {
+            let mut days: Vec<Day> = days.into_inner().into_iter().collect();
+            days.sort_unstable();
+            days
+        }
+        None => all_days().collect(),
+    };
+
+    match action {
+        Action::Keygen => keygen(),
+        Action::Encrypt => encrypt(&load_key(), &days),
+        Action::Decrypt => decrypt(&load_key(), &days),
+    }
+}
+
+fn load_key() -> InputKey {
+    InputKey::load().unwrap_or_else(|e| {
+        eprintln!("Failed to load the input key: {e}");
+        process::exit(1);
+    })
+}
+
+fn keygen() {
+    let path = &config().paths.input_key;
+    if Path::new(path).exists() {
+        eprintln!("Key file \"{path}\" already exists. Delete it first to create a new key.");
+        process::exit(1);
+    }
+
+    if let Err(e) = fs::write(path, InputKey::generate().to_hex() + "\n") {
+        eprintln!("Failed to create key file \"{path}\": {e}");
+        process::exit(1);
+    }
+
+    println!("Created key file \"{path}\". Keep it out of version control.");
+    println!("To decrypt inputs elsewhere, e.g. in CI, set {KEY_ENV_VAR} to its contents.");
+}
+
+fn encrypt(key: &InputKey, days: &[Day]) {
+    let paths = &config().paths;
+    let mut count = 0;
+
+    for &day in days {
+        let Ok(plaintext) = fs::read_to_string(paths.input(day)) else {
+            continue;
+        };
+        // NOTE: `cargo scaffold` creates empty inputs, an encrypted one would count as downloaded.
+        if plaintext.is_empty() {
+            continue;
+        }
+
+        let encrypted_path = paths.encrypted_input(day);
+
+        // NOTE: nonces are random, so only re-encrypt changed inputs to keep diffs clean.
+        if let Ok(existing) = fs::read(&encrypted_path) {
+            if key
+                .decrypt(&existing)
+                .is_ok_and(|existing| existing == plaintext)
+            {
+                continue;
+            }
+        }
+
+        match fs::write(&encrypted_path, key.encrypt(&plaintext)) {
+            Ok(()) => {
+                println!("Encrypted input of day {day} to \"{encrypted_path}\".");
+                count += 1;
+            }
+            Err(e) => {
+                eprintln!("Failed to write \"{encrypted_path}\": {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    println!("Encrypted {count} input(s), all others are up to date or empty.");
+}
+
+fn decrypt(key: &InputKey, days: &[Day]) {
+    let paths = &config().paths;
+    let mut count = 0;
+
+    for &day in days {
+        let input_path = paths.input(day);
+        let Ok(data) = fs::read(paths.encrypted_input(day)) else {
+            continue;
+        };
+        // NOTE: `cargo scaffold` creates empty inputs, those are overwritten.
+        if fs::metadata(&input_path).is_ok_and(|metadata| metadata.len() > 0) {
+            continue;
+        }
+
+        let plaintext = match key.decrypt(&data) {
+            Ok(plaintext) => plaintext,
+            Err(e) => {
+                eprintln!("Failed to decrypt input of day {day}: {e}");
+                process::exit(1);
+            }
+        };
+
+        match fs::write(&input_path, plaintext) {
+            Ok(()) => {
+                println!("Decrypted input of day {day} to \"{input_path}\".");
+                count += 1;
+            }
+            Err(e) => {
+                eprintln!("Failed to write \"{input_path}\": {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    println!("Decrypted {count} input(s), existing non-empty inputs were left untouched.");
+}
+
+// --------------------------------------------------------------------------
+
+impl FromStr for Action {
+    type Err = ActionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "encrypt" => Ok(Action::Encrypt),
+            "decrypt" => Ok(Action::Decrypt),
+            "keygen" => Ok(Action::Keygen),
+            _ => Err(ActionFromStrError(s.to_string())),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`Action`].
+#[derive(Debug)]
+pub struct ActionFromStrError(String);
+
+impl std::error::Error for ActionFromStrError {}
+
+impl Display for ActionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown action `{}`, expecting one of `encrypt`, `decrypt` or `keygen`",
+            self.0
+        )
+    }
+}
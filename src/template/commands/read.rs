@@ -1,8 +1,46 @@
-use std::process;
+use std::{env, fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_cli, config::config, markdown, markdown::Renderer, report::Format, Day};
 
-pub fn handle(day: Day) {
+/// Descriptions are wrapped to the terminal width as given by `COLUMNS`, but at most this wide.
+const MAX_WIDTH: usize = 100;
+
+pub fn handle(day: Day, part: Option<u8>) {
+    let path = config().paths.puzzle(day);
+
+    // NOTE: without a stored puzzle, fall back to fetching it via aoc-cli, which needs network.
+    let Ok(puzzle) = fs::read_to_string(&path) else {
+        if part.is_some() {
+            eprintln!("Note: \"{path}\" does not exist, showing the whole puzzle via aoc-cli.");
+        }
+        read_online(day);
+        return;
+    };
+
+    let puzzle = match part {
+        Some(2) => markdown::part_two(&puzzle).unwrap_or_else(|| {
+            eprintln!(
+                "\"{path}\" does not contain part two yet. Solve part one and run `cargo download \
+                {day}` to update it."
+            );
+            process::exit(1);
+        }),
+        _ => &puzzle,
+    };
+
+    let width = env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .map_or(MAX_WIDTH, |columns: usize| columns.min(MAX_WIDTH));
+
+    let renderer = Renderer {
+        width,
+        is_pretty: Format::detect() == Format::Pretty,
+    };
+    println!("{}", renderer.render(puzzle));
+}
+
+fn read_online(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
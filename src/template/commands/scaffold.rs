@@ -4,7 +4,7 @@ use std::{
     process,
 };
 
-use crate::template::Day;
+use crate::template::{config::config, Day};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -28,9 +28,10 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+    let paths = &config().paths;
+    let input_path = paths.input(day);
+    let example_path = paths.example(day);
+    let module_path = paths.bin(day);
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
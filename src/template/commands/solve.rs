@@ -1,8 +1,14 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{dhat_summary::HeapSummary, run_multi::run_multi, DaySet, RunOptions};
+
+pub fn handle(days: DaySet, options: &RunOptions, dhat: bool, submit_part: Option<u8>) {
+    let Some(day) = days.single() else {
+        // NOTE: several days run one after another, like with `cargo all`.
+        run_multi(&days.into_inner(), options).exit_on_failure();
+        return;
+    };
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -12,8 +18,14 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if options.is_release {
+            cmd_args.push("--release".to_string());
+        }
+
+        if options.count_allocs {
+            cmd_args.extend(["--features".to_string(), "count-allocs".to_string()]);
+        }
     }
 
     cmd_args.push("--".to_string());
@@ -23,6 +35,24 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(part) = options.part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if options.variants {
+        cmd_args.push("--variants".to_string());
+    }
+
+    if options.check_determinism {
+        cmd_args.push("--check-determinism".to_string());
+    }
+
+    if let Some(format) = options.format {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -30,5 +60,23 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    if dhat {
+        // NOTE: a failed build or run leaves the profile of an earlier run behind.
+        if status.success() {
+            println!("---");
+            match HeapSummary::read_from_file() {
+                Ok(summary) => summary.print(),
+                Err(e) => eprintln!("Failed to summarize heap profile: {e}"),
+            }
+        } else {
+            eprintln!("Skipping heap profile summary, the solution did not run successfully.");
+        }
+    }
+
+    // NOTE: a failed build, a failed part or disagreeing variants make the solution exit non-zero.
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -0,0 +1,164 @@
+use std::{fs, path::Path};
+
+use crate::template::{
+    all_days,
+    answers::{Answer, Answers},
+    config::config,
+    timings::Timings,
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Answers longer than this are truncated in the table.
+const MAX_ANSWER_WIDTH: usize = 16;
+
+const PART_WIDTH: usize = 30;
+
+/// State of a data file.
+fn file_state(path: &str) -> &'static str {
+    match fs::metadata(path) {
+        Ok(metadata) if metadata.len() > 0 => "✔",
+        Ok(_) => "○",
+        Err(_) => "·",
+    }
+}
+
+fn format_answer(answer: &str) -> String {
+    if answer.contains('\n') {
+        return "▼".into();
+    }
+    if answer.chars().count() > MAX_ANSWER_WIDTH {
+        let truncated: String = answer.chars().take(MAX_ANSWER_WIDTH - 1).collect();
+        return format!("{truncated}…");
+    }
+    answer.into()
+}
+
+/// Describes a part as `<symbol> <answer> [<incorrect submissions>✗] (<time>)`.
+fn part_state(answer: Option<&Answer>, timing: Option<&String>) -> String {
+    let mut state = match answer {
+        None => "?".to_string(),
+        Some(answer) => match (&answer.last_answer, answer.correct_answer()) {
+            (Some(last), Some(correct)) if last == correct => format!("★ {}", format_answer(last)),
+            (Some(last), Some(_)) => format!("! {}", format_answer(last)),
+            (Some(last), None) => format!("✔ {}", format_answer(last)),
+            (None, Some(correct)) => format!("✖ ({})", format_answer(correct)),
+            (None, None) => "✖".to_string(),
+        },
+    };
+
+    if let Some(incorrect) = answer.map(Answer::incorrect_submissions) {
+        if incorrect > 0 {
+            state.push_str(&format!(" [{incorrect}✗]"));
+        }
+    }
+
+    if let Some(timing) = timing {
+        state.push_str(&format!(" ({timing})"));
+    }
+
+    state
+}
+
+fn print_day(day: Day, answers: &Answers, timings: &Timings) {
+    let paths = &config().paths;
+    let bin = if Path::new(&paths.bin(day)).exists() {
+        "✔"
+    } else {
+        "·"
+    };
+    let input = file_state(&paths.input(day));
+    let example = file_state(&paths.example(day));
+    let puzzle = file_state(&paths.puzzle(day));
+
+    let timing = timings.data.iter().find(|t| t.day == day);
+    let part_1 = part_state(answers.get(day, 1), timing.and_then(|t| t.part_1.as_ref()));
+    let part_2 = part_state(answers.get(day, 2), timing.and_then(|t| t.part_2.as_ref()));
+
+    println!(
+        "{day}   {bin}    {input}      {example}        {puzzle}       {part_1:<PART_WIDTH$} {part_2}"
+    );
+}
+
+pub fn handle() {
+    let answers = Answers::read_from_file().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        Answers::default()
+    });
+    let timings = Timings::read_from_file();
+
+    let stars = answers
+        .data
+        .iter()
+        .filter(|a| a.correct_answer().is_some())
+        .count();
+
+    println!(
+        "{ANSI_BOLD}Day  Bin  Input  Example  Puzzle  {:<PART_WIDTH$} Part 2{ANSI_RESET}",
+        "Part 1"
+    );
+    for day in all_days() {
+        print_day(day, &answers, &timings);
+    }
+
+    println!();
+    println!("{ANSI_BOLD}★ {stars}/50{ANSI_RESET}");
+    println!(
+        "Files: ✔ present, ○ empty, · missing. Parts: ★ accepted, ✔ answered, ! differs from the \
+        accepted answer, ✖ not solved, ? not run yet, [n✗] incorrect submissions."
+    );
+}
+
+#[cfg(all(feature = "test_lib", test))]
+mod tests {
+    use super::{format_answer, part_state};
+    use crate::{
+        day,
+        template::answers::{Answer, Submission, Verdict},
+    };
+
+    fn get_mock_answer(last_answer: Option<&str>) -> Answer {
+        Answer {
+            day: day!(1),
+            part: 1,
+            last_answer: last_answer.map(Into::into),
+            submissions: vec![
+                Submission {
+                    answer: "41".into(),
+                    verdict: Verdict::Incorrect,
+                },
+                Submission {
+                    answer: "42".into(),
+                    verdict: Verdict::Correct,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn describes_parts() {
+        let timing = "1.2ms".to_string();
+        assert_eq!(
+            part_state(Some(&get_mock_answer(Some("42"))), Some(&timing)),
+            "★ 42 [1✗] (1.2ms)"
+        );
+        assert_eq!(
+            part_state(Some(&get_mock_answer(Some("43"))), None),
+            "! 43 [1✗]"
+        );
+        assert_eq!(
+            part_state(Some(&get_mock_answer(None)), None),
+            "✖ (42) [1✗]"
+        );
+        assert_eq!(part_state(None, None), "?");
+    }
+
+    #[test]
+    fn shortens_answers() {
+        assert_eq!(format_answer("12345"), "12345");
+        assert_eq!(format_answer("a\nb"), "▼");
+        assert_eq!(
+            format_answer("abcdefghijklmnopqrstuvwxyz"),
+            "abcdefghijklmno…"
+        );
+    }
+}
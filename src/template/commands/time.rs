@@ -1,11 +1,15 @@
-use std::collections::HashSet;
+use crate::template::{
+    all_days, readme_benchmarks,
+    report::{Format, PartReport},
+    run_multi::{child_commands, run_multi},
+    timings::Timings,
+    DaySet, RunOptions,
+};
 
-use crate::template::{all_days, readme_benchmarks, run_multi::run_multi, timings::Timings, Day};
-
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(days: Option<DaySet>, run_all: bool, store: bool, options: &RunOptions) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
+    let days_to_run = days.map_or_else(
         || {
             if run_all {
                 all_days().collect()
@@ -16,18 +20,36 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        DaySet::into_inner,
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // NOTE: the counting allocator slows down every allocation, so parts are benched without it
+    // and memory usage is measured in a separate run.
+    let count_allocs = options.count_allocs;
+    let options = RunOptions {
+        is_timed: true,
+        is_release: true,
+        count_allocs: false,
+        ..*options
+    };
+    let mut run = run_multi(&days_to_run, &options);
+    let mut timings = run.timings.take().unwrap();
+
+    if count_allocs {
+        measure_memory(&mut timings, &options);
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
-        println!();
         match readme_benchmarks::update(merged_timings) {
+            // NOTE: keep stdout parseable for machine-readable formats.
+            Ok(()) if options.format().is_machine_readable() => {
+                eprintln!("Stored updated benchmarks.");
+            }
             Ok(()) => {
+                println!();
                 println!("Stored updated benchmarks.");
             }
             Err(_) => {
@@ -35,4 +57,56 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    run.exit_on_failure();
+}
+
+/// Run the timed days once more without benching, using the counting allocator to record and
+/// print their memory usage.
+fn measure_memory(timings: &mut Timings, options: &RunOptions) {
+    // NOTE: keep stdout parseable for machine-readable formats.
+    let is_machine_readable = options.format().is_machine_readable();
+    let print = |line: String| {
+        if is_machine_readable {
+            eprintln!("{line}");
+        } else {
+            println!("{line}");
+        }
+    };
+
+    let options = RunOptions {
+        is_timed: false,
+        count_allocs: true,
+        format: Some(Format::Json),
+        variants: false,
+        ..*options
+    };
+
+    print(String::new());
+    print("Memory usage:".into());
+
+    for timing in &mut timings.data {
+        let Ok((output, _)) = child_commands::run_solution(timing.day, &options) else {
+            eprintln!("Failed to measure memory usage of day {}.", timing.day);
+            continue;
+        };
+
+        output
+            .iter()
+            .filter_map(|line| PartReport::from_json_line(line))
+            .filter(|report| report.variant.is_none())
+            .for_each(|report| {
+                if let Some(memory) = &report.memory {
+                    print(format!(
+                        "Day {} part {}: [{memory}]",
+                        timing.day, report.part
+                    ));
+                }
+                match report.part {
+                    1 => timing.part_1_memory = report.memory,
+                    2 => timing.part_2_memory = report.memory,
+                    _ => {}
+                }
+            });
+    }
 }
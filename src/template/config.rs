@@ -0,0 +1,392 @@
+/// Project configuration, loaded from an optional `aoc.toml` in the project root.
+/// All files managed by the template are resolved through [`Paths`], so their locations can be
+/// changed in one place.
+use std::{collections::HashMap, env, fmt::Display, fs, sync::OnceLock};
+
+use crate::template::{report::Format, Day};
+
+static CONFIG_FILE_PATH: &str = "aoc.toml";
+
+/// Placeholder for the zero-padded day number in path templates.
+const DAY_PLACEHOLDER: &str = "{day}";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// The puzzle year. The `AOC_YEAR` environment variable takes precedence if set.
+    pub year: Option<u16>,
+    pub paths: Paths,
+    pub runner: RunnerDefaults,
+}
+
+/// Locations of the files managed by the template. Per-day paths are templates containing `{day}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Paths {
+    pub bins: String,
+    pub inputs: String,
+    /// Encrypted inputs, used when the plaintext input is missing. See `cargo inputs`.
+    pub encrypted_inputs: String,
+    /// File containing the key for encrypted inputs, unless `AOC_INPUT_KEY` is set.
+    pub input_key: String,
+    pub examples: String,
+    pub puzzles: String,
+    pub timings: String,
+    pub answers: String,
+    pub readme: String,
+}
+
+/// Defaults for the flags of `solve`, `all` and `time`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunnerDefaults {
+    pub release: bool,
+    pub memory: bool,
+    pub format: Option<Format>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            paths: Paths {
+                bins: "./src/bin/{day}.rs".into(),
+                inputs: "data/inputs/{day}.txt".into(),
+                encrypted_inputs: "data/inputs/{day}.txt.enc".into(),
+                input_key: ".aoc-input-key".into(),
+                examples: "data/examples/{day}.txt".into(),
+                puzzles: "data/puzzles/{day}.md".into(),
+                timings: "./data/timings.json".into(),
+                answers: "./data/answers.json".into(),
+                readme: "README.md".into(),
+            },
+            runner: RunnerDefaults::default(),
+        }
+    }
+}
+
+/// The configuration of the current project, loaded once. Falls back to the defaults if
+/// `aoc.toml` does not exist or is invalid.
+pub fn config() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        let Ok(s) = fs::read_to_string(CONFIG_FILE_PATH) else {
+            return Config::default();
+        };
+
+        Config::try_from(s.as_str()).unwrap_or_else(|e| {
+            eprintln!("Failed to load {CONFIG_FILE_PATH}: {e}. Using the default configuration.");
+            Config::default()
+        })
+    })
+}
+
+impl Config {
+    /// The puzzle year from the `AOC_YEAR` environment variable or the config file.
+    pub fn year(&self) -> Option<u16> {
+        match env::var("AOC_YEAR") {
+            Ok(x) => x.parse().ok(),
+            Err(_) => self.year,
+        }
+    }
+}
+
+impl Paths {
+    pub fn bin(&self, day: Day) -> String {
+        resolve(&self.bins, day, None)
+    }
+
+    pub fn input(&self, day: Day) -> String {
+        resolve(&self.inputs, day, None)
+    }
+
+    pub fn encrypted_input(&self, day: Day) -> String {
+        resolve(&self.encrypted_inputs, day, None)
+    }
+
+    pub fn example(&self, day: Day) -> String {
+        resolve(&self.examples, day, None)
+    }
+
+    pub fn puzzle(&self, day: Day) -> String {
+        resolve(&self.puzzles, day, None)
+    }
+
+    /// Path of a data file as read by [`read_file`](crate::template::read_file), e.g. `inputs` or
+    /// `examples`. With a part, the day is suffixed, e.g. `01-2.txt`.
+    pub fn data_file(&self, folder: &str, day: Day, part: Option<u8>) -> String {
+        let template = match folder {
+            "inputs" => self.inputs.clone(),
+            "examples" => self.examples.clone(),
+            "puzzles" => self.puzzles.clone(),
+            folder => format!("data/{folder}/{DAY_PLACEHOLDER}.txt"),
+        };
+        resolve(&template, day, part)
+    }
+}
+
+fn resolve(template: &str, day: Day, part: Option<u8>) -> String {
+    match part {
+        Some(part) => template.replace(DAY_PLACEHOLDER, &format!("{day}-{part}")),
+        None => template.replace(DAY_PLACEHOLDER, &day.to_string()),
+    }
+}
+
+// --------------------------------------------------------------------------
+
+/// A value of the supported TOML subset.
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::String(s) => write!(f, "{s:?}"),
+            Value::Integer(i) => write!(f, "{i}"),
+            Value::Boolean(b) => write!(f, "{b}"),
+        }
+    }
+}
+
+/// Parses the subset of TOML used by `aoc.toml`: tables, comments and `key = value` pairs with
+/// string, integer and boolean values. Returns the values by their dotted key, e.g. `paths.inputs`.
+fn parse_toml(s: &str) -> Result<HashMap<String, Value>, String> {
+    let mut values = HashMap::new();
+    let mut table = String::new();
+
+    for (i, line) in s.lines().enumerate() {
+        let line_number = i + 1;
+        let line = strip_comment(line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name
+                .strip_suffix(']')
+                .ok_or_else(|| format!("line {line_number}: unterminated table header"))?;
+            table = name.trim().to_string();
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("line {line_number}: expected `key = value`"))?;
+
+        let key = key.trim();
+        let key = if table.is_empty() {
+            key.to_string()
+        } else {
+            format!("{table}.{key}")
+        };
+
+        let value = parse_value(value.trim())
+            .ok_or_else(|| format!("line {line_number}: invalid value for `{key}`"))?;
+
+        if values.insert(key.clone(), value).is_some() {
+            return Err(format!("line {line_number}: duplicate key `{key}`"));
+        }
+    }
+
+    Ok(values)
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            ('#', None) => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(s: &str) -> Option<Value> {
+    if let Some(literal) = s.strip_prefix('\'') {
+        return Some(Value::String(literal.strip_suffix('\'')?.to_string()));
+    }
+
+    if let Some(basic) = s.strip_prefix('"') {
+        let basic = basic.strip_suffix('"')?;
+        let mut value = String::new();
+        let mut chars = basic.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                value.push(c);
+                continue;
+            }
+            value.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                c @ ('"' | '\\') => c,
+                _ => return None,
+            });
+        }
+        return Some(Value::String(value));
+    }
+
+    match s {
+        "true" => Some(Value::Boolean(true)),
+        "false" => Some(Value::Boolean(false)),
+        s => s.replace('_', "").parse().ok().map(Value::Integer),
+    }
+}
+
+impl TryFrom<&str> for Config {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut config = Config::default();
+
+        for (key, value) in parse_toml(value)? {
+            let invalid = || format!("invalid value {value} for `{key}`");
+
+            match (key.as_str(), &value) {
+                ("year", Value::Integer(year)) => {
+                    config.year = Some(u16::try_from(*year).map_err(|_| invalid())?);
+                }
+                ("paths.bins", Value::String(s)) => config.paths.bins.clone_from(s),
+                ("paths.inputs", Value::String(s)) => config.paths.inputs.clone_from(s),
+                ("paths.encrypted_inputs", Value::String(s)) => {
+                    config.paths.encrypted_inputs.clone_from(s);
+                }
+                ("paths.input_key", Value::String(s)) => config.paths.input_key.clone_from(s),
+                ("paths.examples", Value::String(s)) => config.paths.examples.clone_from(s),
+                ("paths.puzzles", Value::String(s)) => config.paths.puzzles.clone_from(s),
+                ("paths.timings", Value::String(s)) => config.paths.timings.clone_from(s),
+                ("paths.answers", Value::String(s)) => config.paths.answers.clone_from(s),
+                ("paths.readme", Value::String(s)) => config.paths.readme.clone_from(s),
+                ("runner.release", Value::Boolean(b)) => config.runner.release = *b,
+                ("runner.memory", Value::Boolean(b)) => config.runner.memory = *b,
+                ("runner.format", Value::String(s)) => {
+                    config.runner.format = Some(s.parse().map_err(|e| format!("{e}"))?);
+                }
+                (
+                    "year"
+                    | "paths.bins"
+                    | "paths.inputs"
+                    | "paths.encrypted_inputs"
+                    | "paths.input_key"
+                    | "paths.examples"
+                    | "paths.puzzles"
+                    | "paths.timings"
+                    | "paths.answers"
+                    | "paths.readme"
+                    | "runner.release"
+                    | "runner.memory"
+                    | "runner.format",
+                    _,
+                ) => return Err(invalid()),
+                _ => return Err(format!("unknown key `{key}`")),
+            }
+        }
+
+        let paths = &config.paths;
+        for (key, template) in [
+            ("bins", &paths.bins),
+            ("inputs", &paths.inputs),
+            ("encrypted_inputs", &paths.encrypted_inputs),
+            ("examples", &paths.examples),
+            ("puzzles", &paths.puzzles),
+        ] {
+            if !template.contains(DAY_PLACEHOLDER) {
+                return Err(format!("`paths.{key}` must contain `{DAY_PLACEHOLDER}`"));
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+// --------------------------------------------------------------------------
+
+#[cfg(all(feature = "test_lib", test))]
+mod tests {
+    use super::{parse_toml, Config, Value};
+    use crate::{day, template::report::Format};
+
+    #[test]
+    fn parses_toml_subset() {
+        let values = parse_toml(
+            r#"
+            # comment
+            year = 2_024
+
+            [paths]
+            inputs = "inputs/{day}.txt" # trailing comment
+            puzzles = 'puzzles/#{day}.md'
+
+            [runner]
+            release = true
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(values["year"], Value::Integer(2024));
+        assert_eq!(
+            values["paths.inputs"],
+            Value::String("inputs/{day}.txt".into())
+        );
+        assert_eq!(
+            values["paths.puzzles"],
+            Value::String("puzzles/#{day}.md".into())
+        );
+        assert_eq!(values["runner.release"], Value::Boolean(true));
+    }
+
+    #[test]
+    fn loads_config() {
+        let config = Config::try_from(
+            r#"
+            year = 2023
+
+            [paths]
+            inputs = "inputs/{day}.txt"
+
+            [runner]
+            format = "plain"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.paths.input(day!(1)), "inputs/01.txt");
+        assert_eq!(
+            config.paths.data_file("inputs", day!(1), Some(2)),
+            "inputs/01-2.txt"
+        );
+        assert_eq!(config.paths.example(day!(9)), "data/examples/09.txt");
+        assert_eq!(
+            config.paths.encrypted_input(day!(9)),
+            "data/inputs/09.txt.enc"
+        );
+        assert_eq!(config.runner.format, Some(Format::Plain));
+        assert!(!config.runner.release);
+    }
+
+    #[test]
+    fn defaults_without_config() {
+        let config = Config::try_from("").unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.paths.bin(day!(1)), "./src/bin/01.rs");
+        assert_eq!(
+            config.paths.data_file("examples", day!(1), Some(2)),
+            "data/examples/01-2.txt"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert!(Config::try_from("yaer = 2023").is_err());
+        assert!(Config::try_from("year = \"2023\"").is_err());
+        assert!(Config::try_from("[paths]\ninputs = \"inputs.txt\"").is_err());
+        assert!(Config::try_from("[runner]\nformat = \"xml\"").is_err());
+        assert!(Config::try_from("year = 2023\nyear = 2024").is_err());
+        assert!(Config::try_from("[paths\ninputs = 1").is_err());
+    }
+}
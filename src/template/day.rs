@@ -1,4 +1,4 @@
-use std::{error::Error, fmt::Display, str::FromStr};
+use std::{collections::HashSet, error::Error, fmt::Display, str::FromStr};
 
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
@@ -132,6 +132,85 @@ impl Iterator for AllDays {
 
 // --------------------------------------------------------------------------
 
+/// A set of days, parsed from a comma-separated list of days and ranges (e.g. `1-5,9,12`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet(HashSet<Day>);
+
+impl DaySet {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the only day of the set, or `None` if it contains several.
+    pub fn single(&self) -> Option<Day> {
+        match self.0.len() {
+            1 => self.0.iter().next().copied(),
+            _ => None,
+        }
+    }
+
+    /// Converts the [`DaySet`] into a [`HashSet`] of days.
+    pub fn into_inner(self) -> HashSet<Day> {
+        self.0
+    }
+}
+
+impl From<Day> for DaySet {
+    fn from(day: Day) -> Self {
+        Self(HashSet::from([day]))
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = HashSet::new();
+
+        for item in s.split(',') {
+            let parse =
+                |s: &str| Day::from_str(s.trim()).map_err(|_| DaySetFromStrError(item.to_string()));
+
+            match item.split_once('-') {
+                Some((start, end)) => {
+                    let (start, end) = (parse(start)?, parse(end)?);
+                    if start > end {
+                        return Err(DaySetFromStrError(item.to_string()));
+                    }
+                    days.extend(all_days().filter(|day| *day >= start && *day <= end));
+                }
+                None => {
+                    days.insert(parse(item)?);
+                }
+            }
+        }
+
+        Ok(Self(days))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day or range `{}`, expecting days between 1 and 25, e.g. `1-5,9,12`",
+            self.0
+        )
+    }
+}
+
+// --------------------------------------------------------------------------
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -152,7 +231,9 @@ macro_rules! day {
 
 #[cfg(all(feature = "test_lib", test))]
 mod tests {
-    use super::{all_days, Day};
+    use std::collections::HashSet;
+
+    use super::{all_days, Day, DaySet};
 
     #[test]
     fn all_days_iterator() {
@@ -185,6 +266,26 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_day_sets() {
+        let days: DaySet = "1-3, 9,12,2".parse().unwrap();
+        assert_eq!(
+            days.into_inner(),
+            HashSet::from([Day(1), Day(2), Day(3), Day(9), Day(12)])
+        );
+        assert_eq!("7".parse::<DaySet>().unwrap().single(), Some(Day(7)));
+        assert_eq!("7-7".parse::<DaySet>().unwrap().single(), Some(Day(7)));
+    }
+
+    #[test]
+    fn rejects_invalid_day_sets() {
+        assert!("".parse::<DaySet>().is_err());
+        assert!("0-3".parse::<DaySet>().is_err());
+        assert!("5-1".parse::<DaySet>().is_err());
+        assert!("1,,2".parse::<DaySet>().is_err());
+        assert!("1-26".parse::<DaySet>().is_err());
+    }
 }
 
 // --------------------------------------------------------------------------
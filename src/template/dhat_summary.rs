@@ -0,0 +1,363 @@
+/// Module that summarizes the `dhat-heap.json` profile written by `cargo solve --dhat`.
+/// See https://docs.rs/dhat for a description of the file format.
+use std::{cmp::Reverse, collections::HashMap, fs, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{memory::format_bytes, ANSI_BOLD, ANSI_RESET};
+
+pub static DHAT_FILE_PATH: &str = "dhat-heap.json";
+
+/// Number of allocation sites to list in the summary.
+const TOP_SITES: usize = 5;
+
+/// Frames of these crates are never reported as the location of an allocation site.
+const INTERNAL_CRATES: [&str; 5] = ["alloc", "core", "std", "dhat", "hashbrown"];
+
+/// Allocation statistics of a single program point (i.e. a unique backtrace).
+#[derive(Clone, Debug, PartialEq)]
+pub struct AllocationSite {
+    pub total_bytes: u64,
+    pub total_blocks: u64,
+    pub peak_bytes: u64,
+    pub peak_blocks: u64,
+    pub end_bytes: u64,
+    pub end_blocks: u64,
+    /// The most relevant frame of the backtrace, preferably in solution code.
+    pub location: String,
+}
+
+/// Summary of a dhat heap profile.
+#[derive(Clone, Debug, Default)]
+pub struct HeapSummary {
+    pub sites: Vec<AllocationSite>,
+}
+
+impl HeapSummary {
+    pub fn read_from_file() -> Result<Self, String> {
+        fs::read_to_string(DHAT_FILE_PATH)
+            .map_err(|e| format!("could not read {DHAT_FILE_PATH}: {e}"))
+            .and_then(HeapSummary::try_from)
+    }
+
+    pub fn total(&self) -> (u64, u64) {
+        self.sum(|s| (s.total_bytes, s.total_blocks))
+    }
+
+    pub fn at_peak(&self) -> (u64, u64) {
+        self.sum(|s| (s.peak_bytes, s.peak_blocks))
+    }
+
+    pub fn at_end(&self) -> (u64, u64) {
+        self.sum(|s| (s.end_bytes, s.end_blocks))
+    }
+
+    fn sum(&self, f: impl Fn(&AllocationSite) -> (u64, u64)) -> (u64, u64) {
+        self.sites
+            .iter()
+            .map(f)
+            .fold((0, 0), |acc, x| (acc.0 + x.0, acc.1 + x.1))
+    }
+
+    /// Allocation sites sorted by total allocated bytes, largest first. Program points that share
+    /// a location are merged, sites in the template itself (e.g. its argument parsing) are left out.
+    pub fn top_sites(&self, n: usize) -> Vec<AllocationSite> {
+        let mut by_location: HashMap<&str, AllocationSite> = HashMap::new();
+        for site in self
+            .sites
+            .iter()
+            .filter(|site| !is_template_location(&site.location))
+        {
+            by_location
+                .entry(&site.location)
+                .and_modify(|merged| {
+                    merged.total_bytes += site.total_bytes;
+                    merged.total_blocks += site.total_blocks;
+                    merged.peak_bytes += site.peak_bytes;
+                    merged.peak_blocks += site.peak_blocks;
+                    merged.end_bytes += site.end_bytes;
+                    merged.end_blocks += site.end_blocks;
+                })
+                .or_insert_with(|| site.clone());
+        }
+
+        let mut sites: Vec<_> = by_location.into_values().collect();
+        sites.sort_by_key(|s| (Reverse(s.total_bytes), s.location.clone()));
+        sites.truncate(n);
+        sites
+    }
+
+    pub fn print(&self) {
+        let (total_bytes, total_blocks) = self.total();
+        let (peak_bytes, peak_blocks) = self.at_peak();
+        let (end_bytes, end_blocks) = self.at_end();
+
+        println!("{ANSI_BOLD}Heap summary{ANSI_RESET}");
+        println!("------------");
+        println!(
+            "Total:     {} in {total_blocks} blocks",
+            format_bytes(total_bytes)
+        );
+        println!(
+            "At t-gmax: {} in {peak_blocks} blocks",
+            format_bytes(peak_bytes)
+        );
+        println!(
+            "At t-end:  {} in {end_blocks} blocks",
+            format_bytes(end_bytes)
+        );
+
+        let top_sites = self.top_sites(TOP_SITES);
+        if top_sites.is_empty() {
+            return;
+        }
+
+        println!();
+        println!("Top allocation sites by bytes:");
+        for (i, site) in top_sites.iter().enumerate() {
+            #[allow(clippy::cast_precision_loss)]
+            let share = 100.0 * site.total_bytes as f64 / total_bytes.max(1) as f64;
+            println!(
+                "{:>2}. {:>10} ({share:>5.1}%) in {:>6} blocks  {}",
+                i + 1,
+                format_bytes(site.total_bytes),
+                site.total_blocks,
+                site.location
+            );
+        }
+    }
+}
+
+/// Splits a frame (`0x1234: name (file:line:col)`) into its description without the instruction
+/// pointer and the name of the crate the symbol belongs to.
+///
+/// For trait impls on generic types (`<T as alloc::Trait>::f`), the crate of the trait is used.
+fn parse_frame(frame: &str) -> (&str, &str) {
+    let description = frame.split_once(": ").map_or(frame, |(_, rest)| rest);
+    let symbol = description.trim_start_matches('<');
+    let symbol = match symbol.split_once(" as ") {
+        Some((self_ty, trait_path)) if !self_ty.contains("::") => trait_path,
+        _ => symbol,
+    };
+    let krate = symbol.split("::").next().unwrap_or_default();
+    (description, krate)
+}
+
+/// Whether a frame belongs to this library or to a solution binary (crate names like `_01`).
+fn is_solution_frame(frame: &str) -> bool {
+    let (_, krate) = parse_frame(frame);
+    krate == "advent_of_code"
+        || krate
+            .strip_prefix('_')
+            .is_some_and(|day| day.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Whether a location is in the template rather than in solution code. The heap profile covers
+/// the whole solution binary, including the runner around its parts.
+fn is_template_location(location: &str) -> bool {
+    location
+        .trim_start_matches('<')
+        .starts_with("advent_of_code::template::")
+}
+
+fn is_internal_frame(frame: &str) -> bool {
+    let (description, krate) = parse_frame(frame);
+    description.starts_with("__rg_") || INTERNAL_CRATES.contains(&krate)
+}
+
+/// Picks the most relevant frame of a backtrace: the first one in solution code if present,
+/// otherwise the first one outside of the allocation machinery.
+fn location(backtrace: &[&str]) -> String {
+    backtrace
+        .iter()
+        .find(|frame| is_solution_frame(frame))
+        .or_else(|| backtrace.iter().find(|frame| !is_internal_frame(frame)))
+        .or(backtrace.first())
+        .map_or("???", |frame| parse_frame(frame).0)
+        .to_string()
+}
+
+// --------------------------------------------------------------------------
+
+impl TryFrom<String> for HeapSummary {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not a valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let frames = json
+            .get("ftbl")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected JSON document to have an array `ftbl`.")?
+            .iter()
+            .map(|v| v.get::<String>().map(String::as_str))
+            .collect::<Option<Vec<_>>>()
+            .ok_or("expected `ftbl` to contain strings.")?;
+
+        let sites = json
+            .get("pps")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected JSON document to have an array `pps`.")?
+            .iter()
+            .map(|pp| AllocationSite::try_from((pp, frames.as_slice())))
+            .collect::<Result<_, _>>()?;
+
+        Ok(HeapSummary { sites })
+    }
+}
+
+impl TryFrom<(&JsonValue, &[&str])> for AllocationSite {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from((value, frames): (&JsonValue, &[&str])) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected program point to be a JSON object.")?;
+
+        // NOTE: only `tb` and `tbk` are present for every profile kind, the rest is heap-only.
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map_or(0, |v| *v as u64)
+        };
+
+        let frame_indices = json
+            .get("fs")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected program point to have an array `fs`.")?;
+
+        let backtrace = frame_indices
+            .iter()
+            .map(|i| {
+                i.get::<f64>()
+                    .and_then(|i| frames.get(*i as usize))
+                    .copied()
+                    .ok_or("Expected `fs` to contain indices into `ftbl`.")
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(AllocationSite {
+            total_bytes: number("tb"),
+            total_blocks: number("tbk"),
+            peak_bytes: number("gb"),
+            peak_blocks: number("gbk"),
+            end_bytes: number("eb"),
+            end_blocks: number("ebk"),
+            location: location(&backtrace),
+        })
+    }
+}
+
+// --------------------------------------------------------------------------
+
+#[cfg(all(feature = "test_lib", test))]
+mod tests {
+    use super::{location, HeapSummary};
+
+    fn get_mock_profile() -> String {
+        r#"{
+            "dhatFileVersion": 2,
+            "mode": "rust-heap",
+            "verb": "Allocated",
+            "bklt": true,
+            "bkacc": false,
+            "tu": "µs",
+            "Mtu": "s",
+            "tuth": 10,
+            "cmd": "target/dhat/06",
+            "pid": 1234,
+            "tg": 100,
+            "te": 200,
+            "pps": [
+                { "tb": 100, "tbk": 2, "tl": 5, "mb": 100, "mbk": 2, "gb": 100, "gbk": 2, "eb": 0, "ebk": 0, "fs": [1, 2, 3, 4] },
+                { "tb": 4000, "tbk": 40, "tl": 5, "mb": 400, "mbk": 4, "gb": 200, "gbk": 2, "eb": 16, "ebk": 1, "fs": [1, 5] }
+            ],
+            "ftbl": [
+                "[root]",
+                "0x1: alloc::raw_vec::finish_grow (alloc/src/raw_vec.rs:1:1)",
+                "0x2: <hashbrown::raw::RawTable<T> as core::clone::Clone>::clone (src/raw/mod.rs:1:1)",
+                "0x3: itertools::Itertools::collect_vec (itertools-0.13.0/src/lib.rs:1:1)",
+                "0x4: _06::trace_path (src/bin/06.rs:29:9)",
+                "0x5: advent_of_code::parse_char_matrix (src/lib.rs:60:5)"
+            ]
+        }"#
+        .to_string()
+    }
+
+    #[test]
+    fn sums_program_points() {
+        let summary = HeapSummary::try_from(get_mock_profile()).unwrap();
+        assert_eq!(summary.total(), (4100, 42));
+        assert_eq!(summary.at_peak(), (300, 4));
+        assert_eq!(summary.at_end(), (16, 1));
+    }
+
+    #[test]
+    fn sorts_sites_by_bytes() {
+        let summary = HeapSummary::try_from(get_mock_profile()).unwrap();
+        let top = summary.top_sites(1);
+        assert_eq!(top.len(), 1);
+        assert_eq!(
+            top[0].location,
+            "advent_of_code::parse_char_matrix (src/lib.rs:60:5)"
+        );
+    }
+
+    #[test]
+    fn merges_sites_by_location() {
+        let mut summary = HeapSummary::try_from(get_mock_profile()).unwrap();
+        summary.sites.push(summary.sites[0].clone());
+        let top = summary.top_sites(5);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[1].total_bytes, 200);
+        assert_eq!(top[1].total_blocks, 4);
+    }
+
+    #[test]
+    fn leaves_out_template_sites() {
+        let mut summary = HeapSummary::try_from(get_mock_profile()).unwrap();
+        let mut site = summary.sites[1].clone();
+        site.total_bytes = 10_000;
+        site.location =
+            "advent_of_code::template::runner::is_part_selected (src/template/runner.rs:34:41)"
+                .into();
+        summary.sites.push(site);
+        let top = summary.top_sites(5);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].total_bytes, 4000);
+        assert_eq!(summary.total(), (14_100, 82));
+    }
+
+    #[test]
+    fn prefers_solution_frames() {
+        let summary = HeapSummary::try_from(get_mock_profile()).unwrap();
+        assert_eq!(
+            summary.sites[0].location,
+            "_06::trace_path (src/bin/06.rs:29:9)"
+        );
+    }
+
+    #[test]
+    fn skips_internal_frames() {
+        let backtrace = [
+            "0x1: __rg_alloc (???:0:0)",
+            "0x2: <T as alloc::slice::hack::ConvertVec>::to_vec (alloc/src/slice.rs:1:1)",
+            "0x3: regex::Regex::new (regex-1.11.1/src/regex/string.rs:1:1)",
+        ];
+        assert_eq!(
+            location(&backtrace),
+            "regex::Regex::new (regex-1.11.1/src/regex/string.rs:1:1)"
+        );
+        assert_eq!(location(&[]), "???");
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_profile() {
+        HeapSummary::try_from(r#"{ "pps": [] }"#.to_string()).unwrap();
+    }
+}
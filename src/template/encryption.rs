@@ -0,0 +1,147 @@
+/// Symmetric encryption of puzzle inputs, so they can be committed without publishing them.
+/// Inputs are encrypted with ChaCha20-Poly1305. The key is read from the `AOC_INPUT_KEY`
+/// environment variable or the key file configured in `aoc.toml`, as 64 hex characters.
+use std::{env, fmt::Display, fs, io};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+use crate::template::config::config;
+
+pub static KEY_ENV_VAR: &str = "AOC_INPUT_KEY";
+
+/// Marks encrypted files and the version of their format.
+const MAGIC: &[u8] = b"AOC1";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum Error {
+    MissingKey,
+    InvalidKey,
+    InvalidFile,
+    /// Decryption failed, either because the key is wrong or the file was tampered with.
+    Decryption,
+    Io(io::Error),
+}
+
+impl std::error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingKey => write!(
+                f,
+                "no key found. Set {KEY_ENV_VAR} or create \"{}\" with `cargo inputs keygen`.",
+                config().paths.input_key
+            ),
+            Error::InvalidKey => write!(f, "the key is not 64 hex characters."),
+            Error::InvalidFile => write!(f, "the file is not an encrypted input."),
+            Error::Decryption => write!(f, "decryption failed, the key is probably wrong."),
+            Error::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+pub struct InputKey(Key);
+
+impl InputKey {
+    /// Load the key from the environment or the key file.
+    pub fn load() -> Result<Self, Error> {
+        let hex = match env::var(KEY_ENV_VAR) {
+            Ok(hex) => hex,
+            Err(_) => fs::read_to_string(&config().paths.input_key).or(Err(Error::MissingKey))?,
+        };
+        Self::from_hex(hex.trim())
+    }
+
+    pub fn generate() -> Self {
+        Self(ChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    fn from_hex(hex: &str) -> Result<Self, Error> {
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(Error::InvalidKey);
+        }
+
+        let bytes = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<Vec<_>, _>>()
+            .or(Err(Error::InvalidKey))?;
+
+        Ok(Self(*Key::from_slice(&bytes)))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// Encrypt a plaintext with a random nonce. The result is `MAGIC || nonce || ciphertext`.
+    pub fn encrypt(&self, plaintext: &str) -> Vec<u8> {
+        let cipher = ChaCha20Poly1305::new(&self.0);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .expect("encryption of in-memory data does not fail");
+
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<String, Error> {
+        let data = data.strip_prefix(MAGIC).ok_or(Error::InvalidFile)?;
+        if data.len() < NONCE_LEN {
+            return Err(Error::InvalidFile);
+        }
+
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let cipher = ChaCha20Poly1305::new(&self.0);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .or(Err(Error::Decryption))?;
+
+        String::from_utf8(plaintext).or(Err(Error::InvalidFile))
+    }
+}
+
+/// Read and decrypt an encrypted input file with the configured key.
+pub fn decrypt_file(path: &str) -> Result<String, Error> {
+    let data = fs::read(path).map_err(Error::Io)?;
+    InputKey::load()?.decrypt(&data)
+}
+
+#[cfg(all(feature = "test_lib", test))]
+mod tests {
+    use super::{Error, InputKey};
+
+    #[test]
+    fn roundtrips_inputs() {
+        let key = InputKey::generate();
+        let encrypted = key.encrypt("1 2 3\n4 5 6\n");
+        assert_eq!(key.decrypt(&encrypted).unwrap(), "1 2 3\n4 5 6\n");
+        // NOTE: nonces are random, so encrypting twice yields different files.
+        assert_ne!(key.encrypt("1 2 3\n4 5 6\n"), encrypted);
+    }
+
+    #[test]
+    fn parses_hex_keys() {
+        let key = InputKey::generate();
+        let parsed = InputKey::from_hex(&key.to_hex()).unwrap();
+        assert_eq!(parsed.to_hex(), key.to_hex());
+        assert!(matches!(InputKey::from_hex("abc"), Err(Error::InvalidKey)));
+        assert!(matches!(
+            InputKey::from_hex(&"zz".repeat(32)),
+            Err(Error::InvalidKey)
+        ));
+    }
+
+    #[test]
+    fn rejects_wrong_keys_and_files() {
+        let encrypted = InputKey::generate().encrypt("42");
+        let key = InputKey::generate();
+        assert!(matches!(key.decrypt(&encrypted), Err(Error::Decryption)));
+        assert!(matches!(key.decrypt(b"42"), Err(Error::InvalidFile)));
+        assert!(matches!(key.decrypt(b"AOC1"), Err(Error::InvalidFile)));
+    }
+}
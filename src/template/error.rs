@@ -0,0 +1,172 @@
+/// A catch-all error type for fallible solutions, in the spirit of `anyhow`.
+/// Any [`std::error::Error`] converts into [`Error`] via `?`, and [`Context`] wraps errors with a
+/// description of what was being done, e.g. which line failed to parse.
+use std::{error::Error as StdError, fmt::Display};
+
+/// Shorthand for the return type of fallible solutions, e.g. `Result<u32>`.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+pub struct Error {
+    inner: Box<dyn StdError + Send + Sync + 'static>,
+    /// Descriptions added via [`Context`], innermost first.
+    context: Vec<String>,
+}
+
+impl Error {
+    /// Create an error from a message, e.g. `Error::msg(format!("invalid line {line}"))`.
+    pub fn msg(message: impl Display) -> Self {
+        Self {
+            inner: Box::new(MessageError(message.to_string())),
+            context: vec![],
+        }
+    }
+
+    /// Wrap the error with a description of what was being done.
+    #[must_use]
+    pub fn context(mut self, context: impl Display) -> Self {
+        self.context.push(context.to_string());
+        self
+    }
+
+    /// The messages of the error and its causes, outermost first.
+    pub fn chain(&self) -> Vec<String> {
+        let mut chain: Vec<String> = self.context.iter().rev().cloned().collect();
+
+        let mut source: Option<&(dyn StdError + 'static)> = Some(self.inner.as_ref());
+        while let Some(error) = source {
+            chain.push(error.to_string());
+            source = error.source();
+        }
+
+        chain
+    }
+}
+
+impl<E: StdError + Send + Sync + 'static> From<E> for Error {
+    fn from(value: E) -> Self {
+        Self {
+            inner: Box::new(value),
+            context: vec![],
+        }
+    }
+}
+
+/// Prints the outermost message, or the whole chain separated by `: ` with `{:#}`.
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            write!(f, "{}", self.chain().join(": "))
+        } else {
+            write!(f, "{}", self.chain()[0])
+        }
+    }
+}
+
+/// Prints the message and its causes, so that `unwrap()` in tests shows the whole chain.
+impl std::fmt::Debug for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let chain = self.chain();
+        write!(f, "{}", chain[0])?;
+        if chain.len() > 1 {
+            write!(f, "\n\nCaused by:")?;
+            for cause in &chain[1..] {
+                write!(f, "\n    {cause}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct MessageError(String);
+
+impl StdError for MessageError {}
+
+impl Display for MessageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Adds context to errors and turns missing values into errors.
+pub trait Context<T> {
+    fn context(self, context: impl Display) -> Result<T>;
+
+    /// Like [`Context::context`], but only builds the description if there is an error.
+    fn with_context<C: Display>(self, f: impl FnOnce() -> C) -> Result<T>;
+}
+
+impl<T, E: Into<Error>> Context<T> for std::result::Result<T, E> {
+    fn context(self, context: impl Display) -> Result<T> {
+        self.map_err(|e| e.into().context(context))
+    }
+
+    fn with_context<C: Display>(self, f: impl FnOnce() -> C) -> Result<T> {
+        self.map_err(|e| e.into().context(f()))
+    }
+}
+
+impl<T> Context<T> for Option<T> {
+    fn context(self, context: impl Display) -> Result<T> {
+        self.ok_or_else(|| Error::msg(context))
+    }
+
+    fn with_context<C: Display>(self, f: impl FnOnce() -> C) -> Result<T> {
+        self.ok_or_else(|| Error::msg(f()))
+    }
+}
+
+/// Returns early with an [`Error`] built from a format string.
+#[macro_export]
+macro_rules! bail {
+    ($($arg:tt)*) => {
+        return Err($crate::template::error::Error::msg(format!($($arg)*)).into())
+    };
+}
+
+#[cfg(all(feature = "test_lib", test))]
+mod tests {
+    use super::{Context, Error, Result};
+
+    fn parse_line(line: &str) -> Result<u32> {
+        let (_, value) = line.split_once(':').context("missing `:`")?;
+        value
+            .trim()
+            .parse::<u32>()
+            .with_context(|| format!("invalid value `{}`", value.trim()))
+    }
+
+    fn fails() -> Result<()> {
+        crate::bail!("failed after {} tries", 3);
+    }
+
+    #[test]
+    fn chains_contexts() {
+        let error = parse_line("a: b").context("line 2").unwrap_err();
+        assert_eq!(
+            error.chain(),
+            vec![
+                "line 2",
+                "invalid value `b`",
+                "invalid digit found in string"
+            ]
+        );
+        assert_eq!(format!("{error}"), "line 2");
+        assert_eq!(
+            format!("{error:#}"),
+            "line 2: invalid value `b`: invalid digit found in string"
+        );
+        assert_eq!(
+            format!("{error:?}"),
+            "line 2\n\nCaused by:\n    invalid value `b`\n    invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn converts_options_and_messages() {
+        assert_eq!(parse_line("a: 42").unwrap(), 42);
+        assert_eq!(parse_line("a").unwrap_err().chain(), vec!["missing `:`"]);
+        assert_eq!(fails().unwrap_err().to_string(), "failed after 3 tries");
+        assert_eq!(Error::msg("oops").chain(), vec!["oops"]);
+    }
+}
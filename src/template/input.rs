@@ -0,0 +1,145 @@
+/// Reading of data files, with errors that name the file and warnings about suspicious inputs.
+use std::{fmt::Display, io};
+
+use crate::template::Day;
+
+/// An error which can be returned when reading a data file, e.g. an input or example.
+#[derive(Debug)]
+pub enum ReadFileError {
+    NotFound {
+        path: String,
+        folder: String,
+        day: Day,
+    },
+    Io {
+        path: String,
+        source: io::Error,
+    },
+    Decryption {
+        path: String,
+        reason: String,
+    },
+}
+
+impl std::error::Error for ReadFileError {}
+
+impl Display for ReadFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadFileError::NotFound { path, folder, day } => {
+                write!(f, "could not find \"{path}\".")?;
+                match folder.as_str() {
+                    "inputs" => write!(f, " Try `cargo download {day}` to download it."),
+                    "examples" => write!(f, " Paste the example of the puzzle into it."),
+                    _ => Ok(()),
+                }
+            }
+            ReadFileError::Io { path, source } => write!(f, "could not read \"{path}\": {source}"),
+            ReadFileError::Decryption { path, reason } => {
+                write!(f, "could not decrypt \"{path}\": {reason}")
+            }
+        }
+    }
+}
+
+/// Something suspicious about an input that is likely to trip up a solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputWarning {
+    /// The input is empty or only contains whitespace, e.g. right after `cargo scaffold`.
+    Empty,
+    /// The input has Windows line endings. They are normalized to `\n` when reading.
+    CrLf,
+    /// Lines end with spaces or tabs. Holds the first such line, starting at 1.
+    TrailingWhitespace { line: usize },
+    /// The input ends with blank lines.
+    TrailingBlankLines,
+}
+
+impl InputWarning {
+    /// Checks a raw input, i.e. before line endings are normalized.
+    pub fn check(input: &str) -> Vec<Self> {
+        if input.trim().is_empty() {
+            return vec![InputWarning::Empty];
+        }
+
+        let mut warnings = vec![];
+
+        if input.contains("\r\n") {
+            warnings.push(InputWarning::CrLf);
+        }
+
+        if let Some(i) = input.lines().position(|line| line.ends_with([' ', '\t'])) {
+            warnings.push(InputWarning::TrailingWhitespace { line: i + 1 });
+        }
+
+        if normalize_line_endings(input).ends_with("\n\n") {
+            warnings.push(InputWarning::TrailingBlankLines);
+        }
+
+        warnings
+    }
+}
+
+impl Display for InputWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputWarning::Empty => write!(f, "is empty"),
+            InputWarning::CrLf => write!(f, "has CRLF line endings, they were converted to LF"),
+            InputWarning::TrailingWhitespace { line } => {
+                write!(f, "has trailing whitespace, first on line {line}")
+            }
+            InputWarning::TrailingBlankLines => write!(f, "ends with blank lines"),
+        }
+    }
+}
+
+/// Converts Windows line endings to `\n`, so solutions can split on `\n` regardless of how the
+/// input was saved.
+pub fn normalize_line_endings(input: &str) -> String {
+    input.replace("\r\n", "\n")
+}
+
+#[cfg(all(feature = "test_lib", test))]
+mod tests {
+    use super::{normalize_line_endings, InputWarning, ReadFileError};
+    use crate::day;
+
+    #[test]
+    fn accepts_clean_inputs() {
+        assert!(InputWarning::check("1 2\n3 4\n").is_empty());
+        assert!(InputWarning::check("1 2\n3 4").is_empty());
+    }
+
+    #[test]
+    fn warns_about_inputs() {
+        assert_eq!(InputWarning::check(""), vec![InputWarning::Empty]);
+        assert_eq!(InputWarning::check(" \n\n"), vec![InputWarning::Empty]);
+        assert_eq!(
+            InputWarning::check("1 2\r\n3 4 \r\n\r\n"),
+            vec![
+                InputWarning::CrLf,
+                InputWarning::TrailingWhitespace { line: 2 },
+                InputWarning::TrailingBlankLines
+            ]
+        );
+    }
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize_line_endings("a\r\nb\r\n"), "a\nb\n");
+        assert_eq!(normalize_line_endings("a\nb"), "a\nb");
+    }
+
+    #[test]
+    fn suggests_download() {
+        let error = ReadFileError::NotFound {
+            path: "data/inputs/01.txt".into(),
+            folder: "inputs".into(),
+            day: day!(1),
+        };
+        assert_eq!(
+            error.to_string(),
+            "could not find \"data/inputs/01.txt\". Try `cargo download 01` to download it."
+        );
+    }
+}
@@ -0,0 +1,431 @@
+/// A small markdown renderer for the puzzle descriptions stored by aoc-cli.
+/// Supports headings, paragraphs, lists, emphasis, inline code, links and code blocks, which is all
+/// that puzzle descriptions use. Text is wrapped to a given width, code blocks are kept as is.
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Indentation of code blocks and list items.
+const INDENT: &str = "    ";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Style {
+    bold: bool,
+    code: bool,
+}
+
+/// A run of text with a single style.
+type Piece = (String, Style);
+
+/// Part of a line that must not be broken, e.g. `*word*,` consists of a bold and a plain piece.
+type Word = Vec<Piece>;
+
+pub struct Renderer {
+    pub width: usize,
+    /// Whether to style text with ANSI escape codes.
+    pub is_pretty: bool,
+}
+
+impl Renderer {
+    pub fn render(&self, markdown: &str) -> String {
+        let mut out: Vec<String> = vec![];
+        let mut paragraph: Vec<&str> = vec![];
+        let mut lines = markdown.lines().peekable();
+
+        while let Some(line) = lines.next() {
+            let trimmed = line.trim();
+
+            if let Some(fence) = ["```", "~~~"].iter().find(|f| trimmed.starts_with(**f)) {
+                self.flush(&mut paragraph, &mut out);
+                for code in lines.by_ref() {
+                    if code.trim().starts_with(fence) {
+                        break;
+                    }
+                    out.push(self.code_line(code));
+                }
+                out.push(String::new());
+            } else if trimmed.is_empty() {
+                self.flush(&mut paragraph, &mut out);
+            } else if paragraph.is_empty() && line.starts_with(INDENT) {
+                out.push(self.code_line(&line[INDENT.len()..]));
+                if lines.peek().is_none_or(|next| !next.starts_with(INDENT)) {
+                    out.push(String::new());
+                }
+            } else if let Some(heading) = atx_heading(trimmed) {
+                self.flush(&mut paragraph, &mut out);
+                self.heading(heading, &mut out);
+            } else if is_setext_underline(trimmed) {
+                if paragraph.is_empty() {
+                    out.push(self.style("─".repeat(self.width.min(40)), ANSI_BOLD));
+                    out.push(String::new());
+                } else {
+                    let heading = paragraph.join(" ");
+                    paragraph.clear();
+                    self.heading(&heading, &mut out);
+                }
+            } else if let Some(item) = list_item(trimmed) {
+                self.flush(&mut paragraph, &mut out);
+                let words = self.words(item);
+                out.extend(self.wrap(&words, "  • ", "    "));
+                if lines
+                    .peek()
+                    .is_none_or(|next| list_item(next.trim()).is_none())
+                {
+                    out.push(String::new());
+                }
+            } else {
+                paragraph.push(trimmed);
+            }
+        }
+
+        self.flush(&mut paragraph, &mut out);
+
+        while out.last().is_some_and(String::is_empty) {
+            out.pop();
+        }
+        out.join("\n")
+    }
+
+    fn flush(&self, paragraph: &mut Vec<&str>, out: &mut Vec<String>) {
+        if paragraph.is_empty() {
+            return;
+        }
+        let words = self.words(&paragraph.join(" "));
+        out.extend(self.wrap(&words, "", ""));
+        out.push(String::new());
+        paragraph.clear();
+    }
+
+    fn heading(&self, text: &str, out: &mut Vec<String>) {
+        let text: String = self
+            .words(text)
+            .iter()
+            .map(|word| word.iter().map(|(s, _)| s.as_str()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join(" ");
+        out.push(self.style(text, ANSI_BOLD));
+        out.push(String::new());
+    }
+
+    fn code_line(&self, line: &str) -> String {
+        format!("{INDENT}{}", self.style(line.to_string(), ANSI_ITALIC))
+    }
+
+    fn style(&self, s: String, ansi: &str) -> String {
+        if self.is_pretty && !s.is_empty() {
+            format!("{ansi}{s}{ANSI_RESET}")
+        } else {
+            s
+        }
+    }
+
+    fn render_word(&self, word: &Word) -> String {
+        word.iter()
+            .map(|(s, style)| match style {
+                Style { code: true, .. } if self.is_pretty => self.style(s.clone(), ANSI_ITALIC),
+                Style { code: true, .. } => format!("`{s}`"),
+                Style { bold: true, .. } => self.style(s.clone(), ANSI_BOLD),
+                _ => s.clone(),
+            })
+            .collect()
+    }
+
+    /// Splits inline markdown into words, keeping the style of each piece.
+    fn words(&self, text: &str) -> Vec<Word> {
+        let mut words: Vec<Word> = vec![];
+        let mut current: Word = vec![];
+
+        for (s, style) in parse_inline(text) {
+            let mut piece = String::new();
+            for c in s.chars() {
+                // NOTE: code spans are never broken, so that they can be copied from the terminal.
+                if c.is_whitespace() && !style.code {
+                    if !piece.is_empty() {
+                        current.push((std::mem::take(&mut piece), style));
+                    }
+                    if !current.is_empty() {
+                        words.push(std::mem::take(&mut current));
+                    }
+                } else {
+                    piece.push(c);
+                }
+            }
+            if !piece.is_empty() {
+                current.push((piece, style));
+            }
+        }
+
+        if !current.is_empty() {
+            words.push(current);
+        }
+        words
+    }
+
+    /// Greedily wraps words to the width, prefixing the first line and indenting the others.
+    fn wrap(&self, words: &[Word], first_prefix: &str, prefix: &str) -> Vec<String> {
+        let width = |word: &Word| -> usize {
+            let code_ticks = if self.is_pretty { 0 } else { 2 };
+            word.iter()
+                .map(|(s, style)| s.chars().count() + if style.code { code_ticks } else { 0 })
+                .sum()
+        };
+
+        let mut lines = vec![];
+        let mut line = first_prefix.to_string();
+        let mut line_width = first_prefix.chars().count();
+        let mut is_line_empty = true;
+
+        for word in words {
+            let word_width = width(word);
+            if !is_line_empty && line_width + 1 + word_width > self.width {
+                lines.push(std::mem::replace(&mut line, prefix.to_string()));
+                line_width = prefix.chars().count();
+                is_line_empty = true;
+            }
+            if !is_line_empty {
+                line.push(' ');
+                line_width += 1;
+            }
+            line.push_str(&self.render_word(word));
+            line_width += word_width;
+            is_line_empty = false;
+        }
+
+        if !is_line_empty {
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+fn push_piece(current: &mut String, style: Style, pieces: &mut Vec<Piece>) {
+    if !current.is_empty() {
+        pieces.push((std::mem::take(current), style));
+    }
+}
+
+/// Parses emphasis, code spans, links and escapes into styled pieces.
+fn parse_inline(text: &str) -> Vec<Piece> {
+    let mut pieces = vec![];
+    let mut style = Style::default();
+    let mut current = String::new();
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                current.push(chars[i + 1]);
+                i += 2;
+            }
+            '`' => {
+                let Some(len) = chars[i + 1..].iter().position(|&c| c == '`') else {
+                    current.push('`');
+                    i += 1;
+                    continue;
+                };
+                push_piece(&mut current, style, &mut pieces);
+                let code: String = chars[i + 1..i + 1 + len].iter().collect();
+                // NOTE: emphasis inside code, e.g. `*x*`, is kept as bold code.
+                for (s, inner) in parse_inline(&code) {
+                    pieces.push((
+                        s,
+                        Style {
+                            bold: style.bold || inner.bold,
+                            code: true,
+                        },
+                    ));
+                }
+                i += len + 2;
+            }
+            '*' => {
+                push_piece(&mut current, style, &mut pieces);
+                style.bold = !style.bold;
+                i += if chars.get(i + 1) == Some(&'*') { 2 } else { 1 };
+            }
+            '[' => {
+                // NOTE: only a `](` that closes this `[` starts the link target.
+                let link = closing_bracket(&chars[i..])
+                    .filter(|&end| chars.get(i + end + 1) == Some(&'('))
+                    .and_then(|end| {
+                        let close = chars[i + end + 2..].iter().position(|&c| c == ')')?;
+                        Some((end, end + 2 + close))
+                    });
+                let Some((end, close)) = link else {
+                    current.push('[');
+                    i += 1;
+                    continue;
+                };
+                push_piece(&mut current, style, &mut pieces);
+                let text: String = chars[i + 1..i + end].iter().collect();
+                for (s, inner) in parse_inline(&text) {
+                    pieces.push((
+                        s,
+                        Style {
+                            bold: style.bold || inner.bold,
+                            code: inner.code,
+                        },
+                    ));
+                }
+                i += close + 1;
+            }
+            c => {
+                current.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    push_piece(&mut current, style, &mut pieces);
+    pieces
+}
+
+/// The offset of the `]` matching the `[` at the start of the given chars, skipping escaped and
+/// nested brackets.
+fn closing_bracket(chars: &[char]) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+fn atx_heading(line: &str) -> Option<&str> {
+    let text = line.trim_start_matches('#');
+    let level = line.len() - text.len();
+    if (1..=6).contains(&level) && (text.is_empty() || text.starts_with(' ')) {
+        Some(text.trim().trim_end_matches('#').trim())
+    } else {
+        None
+    }
+}
+
+fn is_setext_underline(line: &str) -> bool {
+    line.len() >= 3 && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '='))
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    if let Some(item) = ["* ", "- ", "+ "].iter().find_map(|m| line.strip_prefix(m)) {
+        return Some(item);
+    }
+
+    let (number, item) = line.split_once(". ")?;
+    (!number.is_empty() && number.chars().all(|c| c.is_ascii_digit())).then_some(item)
+}
+
+/// Returns the second half of a puzzle description, starting at its `Part Two` heading.
+pub fn part_two(markdown: &str) -> Option<&str> {
+    let heading = markdown.find("--- Part Two ---")?;
+    let start = markdown[..heading]
+        .rfind('\n')
+        .map_or(0, |newline| newline + 1);
+
+    Some(&markdown[start..])
+}
+
+#[cfg(all(feature = "test_lib", test))]
+mod tests {
+    use super::{part_two, Renderer};
+
+    const PUZZLE: &str = r"\--- Day 1: Historian Hysteria ---
+----------
+
+The *Chief Historian* is always present at the big Christmas sleigh launch, \[but\] nobody has
+seen him in [months](https://example.com)!
+
+For example:
+
+```
+3   4
+4   3
+```
+
+* Pair up the *smallest* number.
+* Then the `second` smallest.
+
+Your puzzle answer was `1651298`.
+
+\--- Part Two ---
+----------
+
+Figure out how often each number appears.
+";
+
+    fn plain(width: usize) -> Renderer {
+        Renderer {
+            width,
+            is_pretty: false,
+        }
+    }
+
+    #[test]
+    fn renders_plain_markdown() {
+        assert_eq!(
+            plain(80).render(PUZZLE),
+            "--- Day 1: Historian Hysteria ---
+
+The Chief Historian is always present at the big Christmas sleigh launch, [but]
+nobody has seen him in months!
+
+For example:
+
+    3   4
+    4   3
+
+  • Pair up the smallest number.
+  • Then the `second` smallest.
+
+Your puzzle answer was `1651298`.
+
+--- Part Two ---
+
+Figure out how often each number appears."
+        );
+    }
+
+    #[test]
+    fn wraps_and_styles() {
+        assert_eq!(
+            plain(12).render("* one two three four"),
+            "  • one two\n    three\n    four"
+        );
+
+        let pretty = Renderer {
+            width: 80,
+            is_pretty: true,
+        };
+        assert_eq!(
+            pretty.render("a *b*, `c`"),
+            "a \x1b[1mb\x1b[0m, \x1b[3mc\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn finds_part_two() {
+        let part_two = part_two(PUZZLE).unwrap();
+        assert!(part_two.starts_with(r"\--- Part Two ---"));
+        assert!(super::part_two("\\--- Day 1 ---\n").is_none());
+
+        let crlf = PUZZLE.replace('\n', "\r\n");
+        assert!(super::part_two(&crlf)
+            .unwrap()
+            .starts_with("\\--- Part Two ---\r\n"));
+    }
+
+    #[test]
+    fn pairs_link_brackets() {
+        assert_eq!(plain(80).render("[a] and [b](u)"), "[a] and b");
+        assert_eq!(plain(80).render(r"\[a] and [b](u)"), "[a] and b");
+        assert_eq!(plain(80).render("[[a]](u) [b] (c)"), "[a] [b] (c)");
+    }
+}
@@ -0,0 +1,176 @@
+/// A lightweight counting allocator that records heap usage of solution parts.
+/// Activated via the `count-allocs` feature, which makes `solution!` install it as the global
+/// allocator of the solution binary.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering::Relaxed},
+};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+static BASELINE_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Global allocator that forwards to the system allocator while counting allocations.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT_BYTES.fetch_sub(layout.size() as u64, Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // NOTE: like dhat, a reallocation counts as a new allocation of the full new size.
+            CURRENT_BYTES.fetch_sub(layout.size() as u64, Relaxed);
+            record_alloc(new_size as u64);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: u64) {
+    INSTALLED.store(true, Relaxed);
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    ALLOCATED_BYTES.fetch_add(size, Relaxed);
+    let current = CURRENT_BYTES.fetch_add(size, Relaxed) + size;
+    PEAK_BYTES.fetch_max(current, Relaxed);
+}
+
+/// Heap usage of a single solution part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Number of allocations (including reallocations).
+    pub allocations: u64,
+    /// Sum of the sizes of all allocations.
+    pub allocated_bytes: u64,
+    /// Maximum heap size reached, relative to the heap size when the part started.
+    pub peak_bytes: u64,
+}
+
+/// Run `func` and return its heap usage. Returns `None` for the usage if the counting allocator
+/// is not installed as the global allocator.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<MemoryUsage>) {
+    let baseline = CURRENT_BYTES.load(Relaxed);
+    BASELINE_BYTES.store(baseline, Relaxed);
+    PEAK_BYTES.store(baseline, Relaxed);
+    ALLOCATIONS.store(0, Relaxed);
+    ALLOCATED_BYTES.store(0, Relaxed);
+
+    let result = func();
+
+    let usage = MemoryUsage {
+        allocations: ALLOCATIONS.load(Relaxed),
+        allocated_bytes: ALLOCATED_BYTES.load(Relaxed),
+        peak_bytes: PEAK_BYTES
+            .load(Relaxed)
+            .saturating_sub(BASELINE_BYTES.load(Relaxed)),
+    };
+
+    (result, INSTALLED.load(Relaxed).then_some(usage))
+}
+
+impl Display for MemoryUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.allocated_bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_bytes(s: &str) -> Option<u64> {
+    let (value, unit) = s.trim().split_once(' ')?;
+    let value: f64 = value.parse().ok()?;
+    let exponent = UNITS.iter().position(|u| *u == unit)?;
+    Some((value * 1024_f64.powi(exponent as i32)).round() as u64)
+}
+
+/// Parse the memory annotation printed by the runner, e.g. `[3 allocs, 1.5 KiB allocated, 1.0 KiB
+/// peak]`. Byte counts are only as precise as the printed value.
+pub fn parse_memory_usage(line: &str) -> Option<MemoryUsage> {
+    let inner = line.rsplit_once('[')?.1.split(']').next()?;
+    let mut parts = inner.split(", ");
+
+    let allocations = parts.next()?.strip_suffix(" allocs")?.parse().ok()?;
+    let allocated_bytes = parse_bytes(parts.next()?.strip_suffix(" allocated")?)?;
+    let peak_bytes = parse_bytes(parts.next()?.strip_suffix(" peak")?)?;
+
+    Some(MemoryUsage {
+        allocations,
+        allocated_bytes,
+        peak_bytes,
+    })
+}
+
+#[cfg(all(feature = "test_lib", test))]
+mod tests {
+    use super::{format_bytes, parse_memory_usage, MemoryUsage};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn roundtrips_memory_usage() {
+        let usage = MemoryUsage {
+            allocations: 12,
+            allocated_bytes: 1536,
+            peak_bytes: 512,
+        };
+        let line = format!("Part 1: 42 (1.0ms @ 10 samples) [{usage}]");
+        assert_eq!(parse_memory_usage(&line), Some(usage));
+    }
+
+    #[test]
+    fn ignores_lines_without_memory_usage() {
+        assert_eq!(parse_memory_usage("Part 1: 42 (1.0ms @ 10 samples)"), None);
+        assert_eq!(parse_memory_usage("Part 1: [1, 2, 3] (1.0ms)"), None);
+    }
+}
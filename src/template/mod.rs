@@ -1,14 +1,29 @@
-use std::{env, fs};
+use std::{env, fs, io, path::Path, process};
+
+use config::config;
 
 pub mod aoc_cli;
+pub mod cli;
 pub mod commands;
+pub mod config;
+pub mod error;
+pub mod memory;
+pub mod report;
 pub mod runner;
 
 pub use day::*;
+pub use input::{normalize_line_endings, InputWarning, ReadFileError};
+pub use run_multi::RunOptions;
 
+mod answers;
 mod day;
+mod dhat_summary;
+mod encryption;
+mod input;
+mod markdown;
 mod readme_benchmarks;
 mod run_multi;
+mod silence;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -16,42 +31,169 @@ pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+///
+/// Line endings are normalized to `\n`. If an input is missing or empty, its encrypted counterpart
+/// is decrypted instead (see `cargo inputs`).
+///
+/// # Panics
+///
+/// Panics with a [`ReadFileError`] describing the problem if the file cannot be read.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file(folder, day, None).unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+///
+/// # Panics
+///
+/// Panics with a [`ReadFileError`] describing the problem if the file cannot be read.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file(folder, day, Some(part)).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Like [`read_file`] and [`read_file_part`], but returns an error instead of panicking.
+pub fn try_read_file(folder: &str, day: Day, part: Option<u8>) -> Result<String, ReadFileError> {
+    read_raw_file(folder, day, part).map(|s| normalize_line_endings(&s))
+}
+
+fn read_raw_file(folder: &str, day: Day, part: Option<u8>) -> Result<String, ReadFileError> {
+    let path = config().paths.data_file(folder, day, part);
+    let filepath = env::current_dir().unwrap().join(&path);
+
+    // NOTE: `cargo scaffold` creates empty inputs, which should not shadow encrypted ones.
+    let is_missing = fs::metadata(&filepath).map_or(true, |metadata| metadata.len() == 0);
+    if folder == "inputs" && part.is_none() && is_missing {
+        let encrypted_path = config().paths.encrypted_input(day);
+        if Path::new(&encrypted_path).exists() {
+            return encryption::decrypt_file(&encrypted_path).map_err(|e| {
+                ReadFileError::Decryption {
+                    path: encrypted_path,
+                    reason: e.to_string(),
+                }
+            });
+        }
+    }
+
+    fs::read_to_string(filepath).map_err(|source| match source.kind() {
+        io::ErrorKind::NotFound => ReadFileError::NotFound {
+            path,
+            folder: folder.to_string(),
+            day,
+        },
+        _ => ReadFileError::Io { path, source },
+    })
+}
+
+/// Reads the puzzle input of a day for a solution binary. Warns about suspicious inputs and exits
+/// with a helpful message if the input cannot be read.
+pub fn read_input(day: Day) -> String {
+    let path = config().paths.input(day);
+    let input = match read_raw_file("inputs", day, None) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+
+    for warning in InputWarning::check(&input) {
+        eprintln!("Warning: \"{path}\" {warning}.");
+        if warning == InputWarning::Empty {
+            eprintln!("Try `cargo download {day}` to download it.");
+        }
+    }
+
+    normalize_line_endings(&input)
+}
+
+/// Like [`eprint!`], but only prints on the first run of a solution part and stays silent while it
+/// is being benched.
+#[macro_export]
+macro_rules! debug_print {
+    ($($arg:tt)*) => {
+        if !$crate::template::runner::is_benching() {
+            eprint!($($arg)*);
+        }
+    };
+}
+
+/// Like [`eprintln!`], but only prints on the first run of a solution part and stays silent while
+/// it is being benched.
+#[macro_export]
+macro_rules! debug_println {
+    ($($arg:tt)*) => {
+        if !$crate::template::runner::is_benching() {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Like [`dbg!`], but only prints on the first run of a solution part and stays silent while it is
+/// being benched. Returns the value of the expression either way.
+#[macro_export]
+macro_rules! debug_dbg {
+    ($val:expr $(,)?) => {
+        match $val {
+            tmp => {
+                if !$crate::template::runner::is_benching() {
+                    eprintln!(
+                        "[{}:{}:{}] {} = {:#?}",
+                        file!(),
+                        line!(),
+                        column!(),
+                        stringify!($val),
+                        &tmp
+                    );
+                }
+                tmp
+            }
+        }
+    };
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
+/// Parts return an `Option` or a `Result` (see [`runner::IntoOutcome`]). If a part fails, the
+/// binary exits with an error after all parts ran.
+///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// At runtime, a single part can also be selected by passing `--part <1|2>` to the binary.
+///
+/// Results are printed in the format passed via `--format` (see [`report::Format`]).
+///
+/// Alternative implementations of a part can be registered as named variants, e.g.
+/// `solution!(13, variants: [(1, "brute", part_one_brute)])`. With `--variants`, they run after
+/// their part and must produce the same answer, otherwise the binary exits with an error.
+///
+/// With `--check-determinism`, each part is rerun several times to check that its answer does not
+/// change, e.g. because it depends on the iteration order of a hash map (see
+/// [`runner::check_determinism`]).
+///
+/// With the `count-allocs` feature enabled, a counting global allocator is installed and the heap
+/// usage of each part is reported next to its timing.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2];);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, 1];);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [part_two, 2];);
+    };
+    ($day:expr, variants: [$( ($vpart:expr, $vname:expr, $vfunc:expr) ),* $(,)?]) => {
+        $crate::solution!(
+            @impl $day, [part_one, 1] [part_two, 2]; $( [$vfunc, $vpart, $vname] )*
+        );
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (
+        @impl $day:expr, $( [$func:expr, $part:expr] )*;
+        $( [$vfunc:expr, $vpart:expr, $vname:expr] )*
+    ) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +201,48 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::memory::CountingAlloc =
+            $crate::template::memory::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::read_input(DAY);
+            let mut reporter = $crate::template::report::Format::from_args().reporter();
+            let variants: Vec<Variant<&str>> = vec![$( Variant::new($vpart, $vname, $vfunc) ),*];
+            let ok = {
+                // NOTE: a single profile covers all parts, it is written when the profiler drops.
+                #[cfg(feature = "dhat-heap")]
+                let _profiler = dhat::Profiler::new_heap();
+
+                let mut ok = true;
+                $(
+                    if is_part_selected($part) {
+                        let outcome = run_part($func, &input, DAY, $part, reporter.as_mut());
+                        let answer = outcome.answer();
+                        ok &= !outcome.is_failed();
+                        if is_determinism_check_mode() {
+                            ok &= check_determinism($func, &input, $part, answer);
+                        }
+                        if is_variants_mode() {
+                            ok &= run_variants(
+                                &variants,
+                                &input,
+                                DAY,
+                                $part,
+                                answer,
+                                reporter.as_mut(),
+                            );
+                        }
+                    }
+                )*
+                ok
+            };
+            reporter.finish(None);
+            if !ok {
+                std::process::exit(1);
+            }
         }
     };
 }
@@ -2,7 +2,7 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::{timings::Timings, Day};
+use crate::template::{config::config, timings::Timings};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -24,11 +24,6 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -63,7 +58,7 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     ];
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = config().paths.bin(timing.day);
         lines.push(format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
@@ -88,7 +83,7 @@ fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+    let path = &config().paths.readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
     update_content(&mut readme, timings, total_millis)?;
@@ -112,18 +107,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 },
             ],
         }
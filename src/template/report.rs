@@ -0,0 +1,621 @@
+/// Reporters render the results of running solutions in one of several output formats.
+/// The format is selected via the `--format` flag of `solve`, `all` and `time` and forwarded to the
+/// solution binaries, which report their parts with the same format.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    io::{stdout, IsTerminal, Write},
+    str::FromStr,
+    time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{memory::MemoryUsage, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// The available output formats.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Colored output that updates lines in place while benching.
+    #[default]
+    Pretty,
+    /// Like `pretty`, but without ANSI escape codes or carriage returns.
+    Plain,
+    /// One JSON object per line.
+    Json,
+    /// A JUnit XML report, printed once all parts have run.
+    Junit,
+}
+
+impl Format {
+    /// The format used if none is given explicitly: `plain` if the `NO_COLOR` environment variable
+    /// is set or stdout is not a terminal, `pretty` otherwise.
+    pub fn detect() -> Self {
+        let no_color = env::var("NO_COLOR").is_ok_and(|v| !v.is_empty());
+        if no_color || !stdout().is_terminal() {
+            Format::Plain
+        } else {
+            Format::Pretty
+        }
+    }
+
+    /// The format passed to the current process via `--format`, falling back to
+    /// [`Format::detect`].
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let Some(value) = args
+            .iter()
+            .position(|x| x == "--format")
+            .and_then(|i| args.get(i + 1))
+        else {
+            return Format::detect();
+        };
+
+        value.parse().unwrap_or_else(|e| {
+            eprintln!("{e}, falling back to the default format.");
+            Format::detect()
+        })
+    }
+
+    /// Whether the output is meant to be consumed by other programs.
+    pub fn is_machine_readable(self) -> bool {
+        matches!(self, Format::Json | Format::Junit)
+    }
+
+    pub fn reporter(self) -> Box<dyn Reporter> {
+        match self {
+            Format::Pretty => Box::new(TextReporter::new(true)),
+            Format::Plain => Box::new(TextReporter::new(false)),
+            Format::Json => Box::new(JsonReporter),
+            Format::Junit => Box::<JunitReporter>::default(),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Format::Pretty => "pretty",
+            Format::Plain => "plain",
+            Format::Json => "json",
+            Format::Junit => "junit",
+        })
+    }
+}
+
+impl FromStr for Format {
+    type Err = FormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pretty" => Ok(Format::Pretty),
+            "plain" => Ok(Format::Plain),
+            "json" => Ok(Format::Json),
+            "junit" => Ok(Format::Junit),
+            _ => Err(FormatFromStrError(s.to_string())),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Format`].
+#[derive(Debug)]
+pub struct FormatFromStrError(String);
+
+impl std::error::Error for FormatFromStrError {}
+
+impl Display for FormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown format `{}`, expecting one of `pretty`, `plain`, `json` or `junit`",
+            self.0
+        )
+    }
+}
+
+// --------------------------------------------------------------------------
+
+/// The outcome of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    pub part: u8,
+    /// The name of the variant, or `None` for the main implementation of the part.
+    pub variant: Option<String>,
+    /// The answer, or `None` if the part is not solved yet or failed.
+    pub answer: Option<String>,
+    /// The error chain of a failed part, e.g. `line 3: invalid digit found in string`.
+    pub error: Option<String>,
+    /// The execution time, averaged over all samples.
+    pub duration: Duration,
+    pub samples: u128,
+    pub memory: Option<MemoryUsage>,
+}
+
+impl PartReport {
+    pub fn is_benched(&self) -> bool {
+        self.samples > 1
+    }
+
+    /// E.g. `Part 1` or `Part 1 (brute)` for a variant.
+    pub fn label(&self) -> String {
+        label(self.part, self.variant.as_deref())
+    }
+}
+
+fn label(part: u8, variant: Option<&str>) -> String {
+    match variant {
+        Some(variant) => format!("Part {part} ({variant})"),
+        None => format!("Part {part}"),
+    }
+}
+
+/// Receives the events of a run and renders them.
+pub trait Reporter {
+    /// Called after the first run of a part, before it is benched.
+    fn part_preview(
+        &mut self,
+        _part: u8,
+        _variant: Option<&str>,
+        _answer: Option<&str>,
+        _is_benched: bool,
+    ) {
+    }
+
+    /// Called once a part is done running.
+    fn part(&mut self, report: &PartReport);
+
+    /// Called before running the solution of a day when running multiple days.
+    fn day_started(&mut self, _day: Day) {}
+
+    /// Called for days without a solution when running multiple days.
+    fn day_unsolved(&mut self, _day: Day) {}
+
+    /// Called once everything has run, with the total run time if the run was benched.
+    fn finish(&mut self, _total_millis: Option<f64>) {}
+}
+
+// --------------------------------------------------------------------------
+
+/// Human readable output, either with ANSI decorations (`pretty`) or without (`plain`).
+pub struct TextReporter {
+    is_pretty: bool,
+    need_space: bool,
+}
+
+impl TextReporter {
+    pub fn new(is_pretty: bool) -> Self {
+        Self {
+            is_pretty,
+            need_space: false,
+        }
+    }
+
+    fn style(&self, s: &str, ansi: &str) -> String {
+        if self.is_pretty {
+            format!("{ansi}{s}{ANSI_RESET}")
+        } else {
+            s.to_string()
+        }
+    }
+}
+
+fn format_duration(duration: &Duration, samples: u128) -> String {
+    if samples == 1 {
+        format!(" ({duration:.1?})")
+    } else {
+        format!(" ({duration:.1?} @ {samples} samples)")
+    }
+}
+
+fn format_memory(memory: &MemoryUsage) -> String {
+    format!(" [{memory}]")
+}
+
+impl Reporter for TextReporter {
+    fn part_preview(
+        &mut self,
+        part: u8,
+        variant: Option<&str>,
+        answer: Option<&str>,
+        is_benched: bool,
+    ) {
+        // NOTE: the preview is overwritten by the final result, which only works on a terminal.
+        if !self.is_pretty {
+            return;
+        }
+
+        let label = label(part, variant);
+        match answer {
+            Some(answer) if answer.contains('\n') => print!("{label}: ▼ "),
+            Some(answer) => print!("{label}: {ANSI_BOLD}{answer}{ANSI_RESET}"),
+            None => print!("{label}: ✖"),
+        }
+
+        if is_benched {
+            print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        }
+        let _ = stdout().flush();
+    }
+
+    fn part(&mut self, report: &PartReport) {
+        let label = report.label();
+        let mut stats = format_duration(&report.duration, report.samples);
+        if let Some(memory) = report.memory {
+            stats.push_str(&format_memory(&memory));
+        }
+
+        if self.is_pretty {
+            print!("\r");
+        }
+
+        match (&report.answer, &report.error) {
+            (_, Some(error)) => {
+                println!("{label}: ✖ {}{stats}", self.style("failed", ANSI_BOLD));
+                println!("  error: {error}");
+            }
+            (Some(answer), None) if answer.contains('\n') => {
+                println!("{label}: ▼ {stats}");
+                println!("{answer}");
+            }
+            (Some(answer), None) => {
+                println!("{label}: {}{stats}", self.style(answer, ANSI_BOLD));
+            }
+            (None, None) if self.is_pretty => println!("{label}: ✖             "),
+            (None, None) => println!("{label}: ✖"),
+        }
+    }
+
+    fn day_started(&mut self, day: Day) {
+        if self.need_space {
+            println!();
+        }
+        self.need_space = true;
+
+        println!("{}", self.style(&format!("Day {day}"), ANSI_BOLD));
+        println!("------");
+    }
+
+    fn day_unsolved(&mut self, _day: Day) {
+        println!("Not solved.");
+    }
+
+    fn finish(&mut self, total_millis: Option<f64>) {
+        if let Some(total_millis) = total_millis {
+            println!(
+                "\n{} {}",
+                self.style("Total (Run):", ANSI_BOLD),
+                self.style(&format!("{total_millis:.2}ms"), ANSI_ITALIC)
+            );
+        }
+    }
+}
+
+// --------------------------------------------------------------------------
+
+/// Prints one JSON object per event, distinguished by their `type` key.
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn part(&mut self, report: &PartReport) {
+        println!("{}", JsonValue::from(report).stringify().unwrap());
+    }
+
+    fn day_unsolved(&mut self, day: Day) {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("type".into(), JsonValue::String("unsolved".into()));
+        map.insert("day".into(), JsonValue::String(day.to_string()));
+        println!("{}", JsonValue::Object(map).stringify().unwrap());
+    }
+
+    fn finish(&mut self, total_millis: Option<f64>) {
+        if let Some(total_millis) = total_millis {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("type".into(), JsonValue::String("total".into()));
+            map.insert("total_millis".into(), JsonValue::Number(total_millis));
+            println!("{}", JsonValue::Object(map).stringify().unwrap());
+        }
+    }
+}
+
+impl From<&PartReport> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("type".into(), JsonValue::String("part".into()));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert(
+            "variant".into(),
+            value
+                .variant
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "memory".into(),
+            value.memory.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part report to be a JSON object.")?;
+
+        if json.get("type").and_then(|v| v.get::<String>()) != Some(&"part".to_string()) {
+            return Err("Expected part report to have type `part`.".into());
+        }
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or_else(|| format!("Expected report.{key} to be a number."))
+        };
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        // NOTE: optional, so that reports of older binaries can still be read.
+        let optional_string = |key: &str| {
+            json.get(key)
+                .filter(|v| !v.is_null())
+                .map(|v| {
+                    v.get::<String>()
+                        .ok_or_else(|| format!("Expected report.{key} to be null or string."))
+                })
+                .transpose()
+        };
+
+        let memory = json
+            .get("memory")
+            .filter(|v| !v.is_null())
+            .map(MemoryUsage::try_from)
+            .transpose()?;
+
+        Ok(PartReport {
+            day,
+            part: number("part")? as u8,
+            variant: optional_string("variant")?.cloned(),
+            answer: answer.cloned(),
+            error: optional_string("error")?.cloned(),
+            duration: Duration::from_nanos(number("nanos")? as u64),
+            samples: number("samples")? as u128,
+            memory,
+        })
+    }
+}
+
+impl PartReport {
+    /// Parse a line printed by the [`JsonReporter`]. Returns `None` for lines that are not part
+    /// reports, e.g. output of the solution itself.
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        let json = JsonValue::from_str(line).ok()?;
+        PartReport::try_from(&json).ok()
+    }
+}
+
+// --------------------------------------------------------------------------
+
+/// Collects all parts and prints a JUnit XML report at the end, with one test suite per day and
+/// one test case per part. Unsolved parts are reported as skipped, failed parts as failures.
+#[derive(Default)]
+pub struct JunitReporter {
+    reports: Vec<PartReport>,
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+impl JunitReporter {
+    fn render(&self) -> String {
+        let mut days: Vec<Day> = self.reports.iter().map(|r| r.day).collect();
+        days.dedup();
+
+        let total_secs: f64 = self.reports.iter().map(|r| r.duration.as_secs_f64()).sum();
+        let (failures, skipped) = count_failures_and_skipped(&self.reports);
+
+        let mut lines = vec![
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+            format!(
+                r#"<testsuites name="advent_of_code" tests="{}" failures="{failures}" skipped="{skipped}" time="{total_secs:.6}">"#,
+                self.reports.len()
+            ),
+        ];
+
+        for day in days {
+            let reports: Vec<_> = self.reports.iter().filter(|r| r.day == day).collect();
+            let secs: f64 = reports.iter().map(|r| r.duration.as_secs_f64()).sum();
+            let (failures, skipped) = count_failures_and_skipped(reports.iter().copied());
+
+            lines.push(format!(
+                r#"  <testsuite name="Day {day}" tests="{}" failures="{failures}" skipped="{skipped}" time="{secs:.6}">"#,
+                reports.len()
+            ));
+
+            for report in reports {
+                let time = report.duration.as_secs_f64();
+                let label = escape_xml(&report.label());
+                lines.push(format!(
+                    r#"    <testcase name="{label}" classname="day{day}" time="{time:.6}">"#
+                ));
+                match (&report.answer, &report.error) {
+                    (_, Some(error)) => lines.push(format!(
+                        r#"      <failure message="{}"/>"#,
+                        escape_xml(error)
+                    )),
+                    (Some(answer), None) => lines.push(format!(
+                        "      <system-out>{}</system-out>",
+                        escape_xml(answer)
+                    )),
+                    (None, None) => lines.push(r#"      <skipped message="not solved"/>"#.into()),
+                }
+                lines.push("    </testcase>".into());
+            }
+
+            lines.push("  </testsuite>".into());
+        }
+
+        lines.push("</testsuites>".into());
+        lines.join("\n")
+    }
+}
+
+fn count_failures_and_skipped<'a>(
+    reports: impl IntoIterator<Item = &'a PartReport>,
+) -> (usize, usize) {
+    reports.into_iter().fold((0, 0), |(failures, skipped), r| {
+        match (&r.answer, &r.error) {
+            (_, Some(_)) => (failures + 1, skipped),
+            (None, None) => (failures, skipped + 1),
+            (Some(_), None) => (failures, skipped),
+        }
+    })
+}
+
+impl Reporter for JunitReporter {
+    fn part(&mut self, report: &PartReport) {
+        self.reports.push(report.clone());
+    }
+
+    fn finish(&mut self, _total_millis: Option<f64>) {
+        println!("{}", self.render());
+    }
+}
+
+// --------------------------------------------------------------------------
+
+#[cfg(all(feature = "test_lib", test))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Format, JunitReporter, PartReport, Reporter};
+    use crate::{day, template::memory::MemoryUsage};
+
+    fn get_mock_report() -> PartReport {
+        PartReport {
+            day: day!(1),
+            part: 2,
+            variant: None,
+            answer: Some("42".into()),
+            error: None,
+            duration: Duration::from_nanos(1500),
+            samples: 10,
+            memory: Some(MemoryUsage {
+                allocations: 1,
+                allocated_bytes: 8,
+                peak_bytes: 8,
+            }),
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("pretty".parse::<Format>().unwrap(), Format::Pretty);
+        assert_eq!("plain".parse::<Format>().unwrap(), Format::Plain);
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert_eq!("junit".parse::<Format>().unwrap(), Format::Junit);
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn roundtrips_json_reports() {
+        let report = get_mock_report();
+        let line = tinyjson::JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(PartReport::from_json_line(&line), Some(report));
+
+        let failed = PartReport {
+            variant: Some("brute".into()),
+            answer: None,
+            error: Some("line 3: invalid digit found in string".into()),
+            ..get_mock_report()
+        };
+        let line = tinyjson::JsonValue::from(&failed).stringify().unwrap();
+        assert_eq!(PartReport::from_json_line(&line), Some(failed));
+    }
+
+    #[test]
+    fn ignores_non_report_lines() {
+        assert_eq!(PartReport::from_json_line("Part 1: 42 (1.0ms)"), None);
+        assert_eq!(
+            PartReport::from_json_line(r#"{ "type": "unsolved", "day": "01" }"#),
+            None
+        );
+    }
+
+    #[test]
+    fn renders_junit_reports() {
+        let mut reporter = JunitReporter::default();
+        reporter.part(&get_mock_report());
+        reporter.part(&PartReport {
+            answer: Some("<a & b>".into()),
+            part: 1,
+            day: day!(2),
+            ..get_mock_report()
+        });
+        reporter.part(&PartReport {
+            answer: None,
+            day: day!(2),
+            ..get_mock_report()
+        });
+        reporter.part(&PartReport {
+            variant: Some("brute".into()),
+            answer: None,
+            error: Some("missing `:`".into()),
+            day: day!(3),
+            ..get_mock_report()
+        });
+
+        let xml = reporter.render();
+        assert!(
+            xml.contains(r#"<testsuites name="advent_of_code" tests="4" failures="1" skipped="1""#)
+        );
+        assert!(xml.contains(r#"<testsuite name="Day 01" tests="1" failures="0" skipped="0""#));
+        assert!(xml.contains(r#"<testsuite name="Day 02" tests="2" failures="0" skipped="1""#));
+        assert!(xml.contains(r#"<testsuite name="Day 03" tests="1" failures="1" skipped="0""#));
+        assert!(xml.contains("<system-out>&lt;a &amp; b&gt;</system-out>"));
+        assert!(xml.contains(r#"<skipped message="not solved"/>"#));
+        assert!(xml.contains(r#"<failure message="missing `:`"/>"#));
+        assert!(xml.contains(r#"<testcase name="Part 2 (brute)" classname="day03""#));
+    }
+}
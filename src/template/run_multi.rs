@@ -1,48 +1,115 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    report::{Format, PartReport},
+    Day,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// How solution binaries are built and run by `solve`, `all` and `time`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Benchmark each part and report its timing.
+    pub is_timed: bool,
+    pub is_release: bool,
+    /// Build with the counting allocator to report allocations and peak heap usage per part.
+    pub count_allocs: bool,
+    /// The output format, detected if not given.
+    pub format: Option<Format>,
+    /// Only run this part.
+    pub part: Option<u8>,
+    /// Also run named variants and check that they agree.
+    pub variants: bool,
+    /// Rerun each part several times and report differing answers.
+    pub check_determinism: bool,
+}
+
+impl RunOptions {
+    /// The output format, falling back to [`Format::detect`].
+    pub fn format(&self) -> Format {
+        self.format.unwrap_or_else(Format::detect)
+    }
+}
+
+/// The outcome of running several days with [`run_multi`].
+pub struct MultiRun {
+    /// The timings of all days, if they were run with `is_timed`.
+    pub timings: Option<Timings>,
+    /// Days whose solution failed to build or exited with an error.
+    pub failed_days: Vec<Day>,
+}
+
+impl MultiRun {
+    /// Exit with an error if any of the days failed.
+    pub fn exit_on_failure(&self) {
+        if !self.failed_days.is_empty() {
+            std::process::exit(1);
+        }
+    }
+}
 
-    let mut need_space = false;
+pub fn run_multi(days_to_run: &HashSet<Day>, options: &RunOptions) -> MultiRun {
+    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut failed_days = vec![];
+    let format = options.format();
+    let mut reporter = format.reporter();
 
     // NOTE: use non-duplicate, sorted day values.
     all_days()
         .filter(|day| days_to_run.contains(day))
         .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            reporter.day_started(day);
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let (output, status) = child_commands::run_solution(day, options).unwrap();
+            if !status.success() {
+                failed_days.push(day);
+            }
 
             if output.is_empty() {
-                println!("Not solved.");
+                reporter.day_unsolved(day);
+            } else if format.is_machine_readable() {
+                // NOTE: machine-readable formats are rendered here from the child's JSON reports,
+                // everything else the child printed is forwarded to stderr.
+                let reports: Vec<PartReport> = output
+                    .iter()
+                    .filter_map(|line| {
+                        let report = PartReport::from_json_line(line);
+                        if report.is_none() {
+                            eprintln!("{line}");
+                        }
+                        report
+                    })
+                    .collect();
+
+                reports.iter().for_each(|report| reporter.part(report));
+                timings.push(child_commands::collect_timing(&reports, day));
             } else {
                 let val = child_commands::parse_exec_time(&output, day);
                 timings.push(val);
             }
         });
 
-    if is_timed {
+    let timings = if options.is_timed {
         let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
+        reporter.finish(Some(timings.total_millis()));
         Some(timings)
     } else {
+        reporter.finish(None);
         None
+    };
+
+    if !failed_days.is_empty() {
+        let days: Vec<String> = failed_days.iter().map(ToString::to_string).collect();
+        eprintln!("Failed days: {}", days.join(", "));
+    }
+
+    MultiRun {
+        timings,
+        failed_days,
     }
 }
 
@@ -59,44 +126,78 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing
 /// the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, RunOptions};
+    use crate::template::{
+        config::config,
+        memory::parse_memory_usage,
+        report::{Format, PartReport},
+        Day,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{Command, ExitStatus, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day, returning its stdout lines and exit status.
+    /// For machine-readable formats, the child reports in JSON and its output is not forwarded.
+    pub fn run_solution(
+        day: Day,
+        options: &RunOptions,
+    ) -> Result<(Vec<String>, ExitStatus), Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&config().paths.bin(day)).exists() {
+            return Ok((vec![], ExitStatus::default()));
         }
 
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
-        if is_release {
+        if options.is_release {
             args.push("--release");
         }
 
-        if is_timed {
+        if options.count_allocs {
+            args.push("--features");
+            args.push("count-allocs");
+        }
+
+        args.push("--");
+
+        if options.is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        let format = options.format();
+        let child_format = if format.is_machine_readable() {
+            Format::Json
+        } else {
+            format
+        }
+        .to_string();
+        args.push("--format");
+        args.push(&child_format);
+
+        let part = options.part.map(|part| part.to_string());
+        if let Some(part) = &part {
+            args.push("--part");
+            args.push(part);
+        }
+
+        if options.variants {
+            args.push("--variants");
+        }
+
+        if options.check_determinism {
+            args.push("--check-determinism");
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -119,14 +220,16 @@ pub mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+            if !format.is_machine_readable() {
+                println!("{line}");
+            }
             output.push(line);
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok((output, status))
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
@@ -135,6 +238,8 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            part_1_memory: None,
+            part_2_memory: None,
         };
 
         output
@@ -150,13 +255,18 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                let memory = parse_memory_usage(l.rsplit(" samples)").next()?);
+                Some((part, timing_str, nanos, memory))
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
+            // NOTE: variants are labeled like `Part 1 (brute)` and do not count towards timings.
+            .filter(|(part, ..)| part.ends_with("Part 1") || part.ends_with("Part 2"))
+            .for_each(|(part, timing_str, nanos, memory)| {
+                if part.ends_with("Part 1") {
                     timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
+                    timings.part_1_memory = memory;
+                } else {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_memory = memory;
                 }
 
                 timings.total_nanos += nanos;
@@ -165,6 +275,37 @@ pub mod child_commands {
         timings
     }
 
+    /// Like [`parse_exec_time`], but for the JSON reports of machine-readable formats.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn collect_timing(reports: &[PartReport], day: Day) -> super::Timing {
+        let mut timings = super::Timing {
+            day,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            part_1_memory: None,
+            part_2_memory: None,
+        };
+
+        reports
+            .iter()
+            .filter(|report| report.is_benched() && report.variant.is_none())
+            .for_each(|report| {
+                let timing_str = Some(format!("{:.1?}", report.duration));
+                if report.part == 1 {
+                    timings.part_1 = timing_str;
+                    timings.part_1_memory = report.memory;
+                } else if report.part == 2 {
+                    timings.part_2 = timing_str;
+                    timings.part_2_memory = report.memory;
+                }
+
+                timings.total_nanos += report.duration.as_nanos() as f64;
+            });
+
+        timings
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -207,9 +348,11 @@ pub mod child_commands {
 
     #[cfg(all(feature = "test_lib", test))]
     mod tests {
-        use super::parse_exec_time;
+        use std::time::Duration;
+
+        use super::{collect_timing, parse_exec_time};
 
-        use crate::day;
+        use crate::{day, template::report::PartReport};
 
         #[test]
         fn parses_execution_times() {
@@ -241,6 +384,73 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_memory_usage() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [3 allocs, 1.5 KiB allocated, 512 B peak]"
+                        .into(),
+                    "Part 2: [1] (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
+            let memory = res.part_1_memory.unwrap();
+            assert_eq!(memory.allocations, 3);
+            assert_eq!(memory.allocated_bytes, 1536);
+            assert_eq!(memory.peak_bytes, 512);
+            assert!(res.part_2_memory.is_none());
+        }
+
+        #[test]
+        fn collects_timings_from_reports() {
+            let report = PartReport {
+                day: day!(1),
+                part: 1,
+                variant: None,
+                answer: Some("0".into()),
+                error: None,
+                duration: Duration::from_micros(1500),
+                samples: 100,
+                memory: None,
+            };
+            let res = collect_timing(
+                &[
+                    report.clone(),
+                    PartReport {
+                        part: 2,
+                        samples: 1,
+                        ..report.clone()
+                    },
+                    PartReport {
+                        variant: Some("brute".into()),
+                        duration: Duration::from_secs(1),
+                        ..report
+                    },
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 1500000_f64);
+            assert_eq!(res.part_1.unwrap(), "1.5ms");
+            assert!(res.part_2.is_none());
+        }
+
+        #[test]
+        fn ignores_variants() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 1 (brute): 0 (1.00s @ 10 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74.13_f64);
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            assert!(res.part_2.is_none());
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
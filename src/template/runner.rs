@@ -1,27 +1,294 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::{
-    cmp, env,
+    cmp,
+    collections::HashMap,
+    env,
     hint::black_box,
-    io::{stdout, Write},
     process,
     process::Output,
+    sync::atomic::{AtomicBool, Ordering},
     time::{Duration, Instant},
 };
 
-use crate::template::{aoc_cli, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    answers::{Answers, Verdict},
+    aoc_cli,
+    error::Error,
+    memory::{self, MemoryUsage},
+    report::{PartReport, Reporter},
+    silence::SilencedOutput,
+    Day,
+};
+
+static IS_BENCHING: AtomicBool = AtomicBool::new(false);
+
+/// Whether a solution part is currently being benched, i.e. executed again after its first run.
+/// Used by [`debug_println!`](crate::debug_println) and friends to only print on the first run.
+pub fn is_benching() -> bool {
+    IS_BENCHING.load(Ordering::Relaxed)
+}
+
+/// Whether a part should run, i.e. no other part was selected via `--part`.
+pub fn is_part_selected(part: u8) -> bool {
+    let args: Vec<String> = env::args().collect();
+    args.iter()
+        .position(|x| x == "--part")
+        .and_then(|i| args.get(i + 1))
+        .is_none_or(|selected| *selected == part.to_string())
+}
+
+/// Whether the registered variants of each part should run as well, i.e. `--variants` was passed.
+pub fn is_variants_mode() -> bool {
+    env::args().any(|x| x == "--variants")
+}
+
+/// How often a part runs when checking its determinism, including its first run.
+pub const DETERMINISM_RUNS: usize = 10;
+
+/// Whether parts should be rerun to check their determinism, i.e. `--check-determinism` was
+/// passed.
+pub fn is_determinism_check_mode() -> bool {
+    env::args().any(|x| x == "--check-determinism")
+}
+
+/// The result of a solution part.
+#[derive(Debug)]
+pub enum Outcome {
+    Solved(String),
+    /// The part is not implemented yet.
+    Unsolved,
+    /// The part is implemented, but failed with an error.
+    Failed(Error),
+}
+
+impl Outcome {
+    pub fn answer(&self) -> Option<&str> {
+        match self {
+            Outcome::Solved(answer) => Some(answer),
+            _ => None,
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Outcome::Failed(_))
+    }
+}
+
+/// Return types of solution parts. `Option` signals an unimplemented part with `None`, `Result`
+/// signals a failed part with an error.
+pub trait IntoOutcome {
+    fn into_outcome(self) -> Outcome;
+}
+
+impl<T: Display> IntoOutcome for Option<T> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Some(answer) => Outcome::Solved(answer.to_string()),
+            None => Outcome::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Into<Error>> IntoOutcome for Result<T, E> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Ok(answer) => Outcome::Solved(answer.to_string()),
+            Err(e) => Outcome::Failed(e.into()),
+        }
+    }
+}
+
+impl IntoOutcome for Outcome {
+    fn into_outcome(self) -> Outcome {
+        self
+    }
+}
+
+/// An alternative implementation of a part, e.g. a brute-force version of an optimized solution.
+/// Registered via `solution!` and run with `--variants`.
+pub struct Variant<I> {
+    pub part: u8,
+    pub name: &'static str,
+    func: Box<dyn Fn(I) -> Outcome>,
+}
+
+impl<I> Variant<I> {
+    pub fn new<R: IntoOutcome>(
+        part: u8,
+        name: &'static str,
+        func: impl Fn(I) -> R + 'static,
+    ) -> Self {
+        Self {
+            part,
+            name,
+            func: Box::new(move |input| func(input).into_outcome()),
+        }
+    }
+}
+
+/// Runs a part and reports it, returning its outcome.
+fn run_and_report<I: Clone, R: IntoOutcome>(
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+    variant: Option<&str>,
+    reporter: &mut dyn Reporter,
+) -> Outcome {
+    let (outcome, duration, samples, memory) = run_timed(func, input, |outcome, is_benched| {
+        reporter.part_preview(part, variant, outcome.answer(), is_benched);
+    });
+
+    reporter.part(&PartReport {
+        day,
+        part,
+        variant: variant.map(ToString::to_string),
+        answer: outcome.answer().map(ToString::to_string),
+        error: match &outcome {
+            Outcome::Failed(e) => Some(format!("{e:#}")),
+            _ => None,
+        },
+        duration,
+        samples,
+        memory,
+    });
+
+    outcome
+}
+
+/// Runs a part, records its answer and submits it if requested. Returns its outcome.
+/// Answers are not recorded while profiling the heap with dhat.
+pub fn run_part<I: Clone, R: IntoOutcome>(
+    func: impl Fn(I) -> R,
+    input: I,
+    day: Day,
+    part: u8,
+    reporter: &mut dyn Reporter,
+) -> Outcome {
+    let outcome = run_and_report(func, input, day, part, None, reporter);
+
+    // NOTE: the heap profile covers the whole process, keep the bookkeeping of answers out of it.
+    if cfg!(feature = "dhat-heap") {
+        return outcome;
+    }
+
+    let answer = outcome.answer().map(ToString::to_string);
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+    // NOTE: an answers file that can't be parsed is left alone instead of being overwritten.
+    let (mut answers, writable) = match Answers::read_from_file() {
+        Ok(answers) => (answers, true),
+        Err(e) => {
+            eprintln!("Not recording answers, {e}");
+            (Answers::default(), false)
+        }
+    };
+    let mut changed = answers.record_run(day, part, answer.clone());
+
+    if let Some(answer) = &answer {
+        if let Some(Ok(output)) = submit_result(answer, day, part) {
+            if let Some(verdict) =
+                Verdict::from_aoc_output(&String::from_utf8_lossy(&output.stdout))
+            {
+                answers.record_submission(day, part, answer.clone(), verdict);
+                changed = true;
+            }
+        }
+    }
+
+    if changed && writable {
+        if let Err(e) = answers.store_file() {
+            eprintln!("Failed to store answers: {e}");
+        }
+    }
+
+    outcome
+}
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Runs the variants of a part and checks that they agree with the answer of the part. Variants
+/// are neither recorded nor submitted. Returns whether all variants agree and none of them failed.
+pub fn run_variants<I: Clone>(
+    variants: &[Variant<I>],
+    input: I,
+    day: Day,
+    part: u8,
+    expected: Option<&str>,
+    reporter: &mut dyn Reporter,
+) -> bool {
+    let mut agree = true;
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    for variant in variants.iter().filter(|v| v.part == part) {
+        let outcome = run_and_report(
+            &variant.func,
+            input.clone(),
+            day,
+            part,
+            Some(variant.name),
+            reporter,
+        );
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        if outcome.answer() != expected {
+            eprintln!(
+                "Variant `{}` of part {part} disagrees: expected {}, got {}.",
+                variant.name,
+                expected.unwrap_or("no answer"),
+                outcome.answer().unwrap_or("no answer")
+            );
+            agree = false;
+        } else if outcome.is_failed() {
+            agree = false;
+        }
     }
+
+    agree
+}
+
+/// Reruns a part and reports to stderr whether all runs produced the same answer as the first one.
+/// Output of the reruns is silenced like while benching. Unsolved and failed runs count as `✖`.
+/// Returns whether the part is deterministic.
+///
+/// Every rerun builds its hash maps with new seeds: both the standard library's `RandomState` and
+/// hashbrown's default hasher derive a fresh seed for each map from a per-thread counter. Seeds
+/// that are fixed per process are not varied.
+pub fn check_determinism<I: Clone, R: IntoOutcome>(
+    func: impl Fn(I) -> R,
+    input: I,
+    part: u8,
+    first: Option<&str>,
+) -> bool {
+    let key = |answer: Option<&str>| answer.unwrap_or("✖").to_string();
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    *counts.entry(key(first)).or_default() += 1;
+
+    IS_BENCHING.store(true, Ordering::Relaxed);
+    let silenced = SilencedOutput::new();
+
+    for _ in 1..DETERMINISM_RUNS {
+        let outcome = func(input.clone()).into_outcome();
+        *counts.entry(key(outcome.answer())).or_default() += 1;
+    }
+
+    drop(silenced);
+    IS_BENCHING.store(false, Ordering::Relaxed);
+
+    if counts.len() == 1 {
+        eprintln!("Part {part}: deterministic over {DETERMINISM_RUNS} runs.");
+        return true;
+    }
+
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let answers: Vec<String> = counts
+        .iter()
+        .map(|(answer, count)| format!("{answer} ({count}×)"))
+        .collect();
+    eprintln!(
+        "Part {part}: non-deterministic, {} different answers in {DETERMINISM_RUNS} runs: {}",
+        counts.len(),
+        answers.join(", ")
+    );
+    false
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug
@@ -29,44 +296,44 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples,
 ///     whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+///
+/// Heap usage is recorded for the first execution if the counting allocator is installed. Failed
+/// parts are not benched.
+fn run_timed<I: Clone, R: IntoOutcome>(
+    func: impl Fn(I) -> R,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    hook: impl FnOnce(&Outcome, bool),
+) -> (Outcome, Duration, u128, Option<MemoryUsage>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
-
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
-
-        func(input)
+        memory::measure(|| func(input))
     };
     let base_time = timer.elapsed();
+    let outcome = result.into_outcome();
 
-    hook(&result);
+    let is_benched =
+        std::env::args().any(|x| x == "--time") && !matches!(outcome, Outcome::Failed(_));
+    hook(&outcome, is_benched);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if is_benched {
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (outcome, run.0, run.1, memory)
 }
 
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
-
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
+    IS_BENCHING.store(true, Ordering::Relaxed);
+    let silenced = SilencedOutput::new();
+
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
@@ -75,6 +342,9 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
+    drop(silenced);
+    IS_BENCHING.store(false, Ordering::Relaxed);
+
     (
         #[allow(clippy::cast_possible_truncation)]
         Duration::from_nanos(average_duration(&timers) as u64),
@@ -90,49 +360,6 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
-    }
-}
-
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
-
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
-            }
-        }
-    }
-}
-
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
@@ -171,3 +398,23 @@ fn submit_result<T: Display>(
     println!("Submitting result via aoc-cli...");
     Some(aoc_cli::submit(day, part, &result.to_string()))
 }
+
+#[cfg(all(feature = "test_lib", test))]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::check_determinism;
+
+    #[test]
+    fn fails_determinism_check_on_differing_answers() {
+        assert!(check_determinism(|n: u32| Some(n * 2), 21, 1, Some("42")));
+
+        // NOTE: the result of `check_determinism` decides the exit status of the solution, which
+        // `solve`, `all` and `time` pass on.
+        let runs = AtomicUsize::new(0);
+        let flaky =
+            |n: u32| Some(n + runs.fetch_add(1, Ordering::Relaxed).is_multiple_of(3) as u32);
+        assert!(!check_determinism(flaky, 42, 2, Some("42")));
+        assert!(!check_determinism(|_: u32| None::<u32>, 0, 2, Some("42")));
+    }
+}
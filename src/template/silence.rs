@@ -0,0 +1,67 @@
+/// Silences the output of solutions while they are being benched.
+/// Solutions are free to print, e.g. for debugging, but doing so for thousands of bench iterations
+/// floods the terminal and pollutes the output that is parsed for timings.
+use std::io::{stdout, Write};
+
+/// Redirects stdout and stderr to the null device for as long as it is alive.
+/// On platforms other than unix, this is a no-op.
+pub struct SilencedOutput {
+    #[cfg(unix)]
+    saved_fds: Vec<(libc::c_int, libc::c_int)>,
+}
+
+#[cfg(unix)]
+impl SilencedOutput {
+    pub fn new() -> Self {
+        use std::{fs::OpenOptions, os::fd::AsRawFd};
+
+        // NOTE: flush first, so that output written before silencing is not lost.
+        let _ = stdout().flush();
+
+        let mut saved_fds = vec![];
+        let Ok(null) = OpenOptions::new().write(true).open("/dev/null") else {
+            return Self { saved_fds };
+        };
+
+        for fd in [libc::STDOUT_FILENO, libc::STDERR_FILENO] {
+            // SAFETY: `dup` and `dup2` only operate on file descriptors owned by this process.
+            unsafe {
+                let saved = libc::dup(fd);
+                if saved < 0 {
+                    continue;
+                }
+                if libc::dup2(null.as_raw_fd(), fd) < 0 {
+                    libc::close(saved);
+                    continue;
+                }
+                saved_fds.push((fd, saved));
+            }
+        }
+
+        Self { saved_fds }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for SilencedOutput {
+    fn drop(&mut self) {
+        // NOTE: discard output that is still buffered instead of printing it after restoring.
+        let _ = stdout().flush();
+
+        for (fd, saved) in self.saved_fds.drain(..) {
+            // SAFETY: `saved` is a duplicate of `fd` created in `new` and owned by this struct.
+            unsafe {
+                libc::dup2(saved, fd);
+                libc::close(saved);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+impl SilencedOutput {
+    pub fn new() -> Self {
+        let _ = stdout().flush();
+        Self {}
+    }
+}
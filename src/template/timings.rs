@@ -1,9 +1,7 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::{config::config, memory::MemoryUsage, Day};
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -12,6 +10,8 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub part_1_memory: Option<MemoryUsage>,
+    pub part_2_memory: Option<MemoryUsage>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -25,13 +25,13 @@ impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(&config().paths.timings)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+        let s = fs::read_to_string(&config().paths.timings)
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -140,6 +140,16 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "part_1_memory".into(),
+            value.part_1_memory.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_memory".into(),
+            value.part_2_memory.map_or(JsonValue::Null, JsonValue::from),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -173,11 +183,74 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: memory usage is optional to stay compatible with timings stored without it.
+        let part_1_memory = json
+            .get("part_1_memory")
+            .filter(|v| !v.is_null())
+            .map(MemoryUsage::try_from)
+            .transpose()?;
+
+        let part_2_memory = json
+            .get("part_2_memory")
+            .filter(|v| !v.is_null())
+            .map(MemoryUsage::try_from)
+            .transpose()?;
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            part_1_memory,
+            part_2_memory,
+        })
+    }
+}
+
+// --------------------------------------------------------------------------
+
+impl From<MemoryUsage> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: MemoryUsage) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert(
+            "allocated_bytes".into(),
+            JsonValue::Number(value.allocated_bytes as f64),
+        );
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryUsage {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory usage to be a JSON object.")?;
+
+        let get_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as u64)
+                .ok_or_else(|| format!("Expected memory usage.{key} to be a number."))
+        };
+
+        Ok(MemoryUsage {
+            allocations: get_number("allocations")?,
+            allocated_bytes: get_number("allocated_bytes")?,
+            peak_bytes: get_number("peak_bytes")?,
         })
     }
 }
@@ -198,18 +271,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 },
             ],
         }
@@ -230,6 +309,18 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_memory_usage() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000, "part_1_memory": { "allocations": 3, "allocated_bytes": 1024, "peak_bytes": 512 }, "part_2_memory": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let memory = timing.part_1_memory.unwrap();
+            assert_eq!(memory.allocations, 3);
+            assert_eq!(memory.allocated_bytes, 1024);
+            assert_eq!(memory.peak_bytes, 512);
+            assert!(timing.part_2_memory.is_none());
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -289,6 +380,8 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 }],
             };
 
@@ -303,6 +396,8 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 }],
             };
 
@@ -317,6 +412,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 }],
             };
 
@@ -341,6 +438,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,6 +460,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_memory: None,
+                    part_2_memory: None,
                 }],
             };
             let merged = timings.merge(&other);